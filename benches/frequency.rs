@@ -0,0 +1,43 @@
+//! Compares the table based `Note::to_frequency` with computing the frequency using `powf`
+//!
+//! Run with `cargo bench --bench frequency`. This is a plain timing loop rather than a benchmark
+//! harness so it builds with the crate's minimum supported Rust version.
+
+use midi_types::Note;
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 100_000;
+
+/// Keeps the optimizer from removing the work being timed
+fn consume(value: f32) {
+    unsafe { core::ptr::read_volatile(&value) };
+}
+
+fn computed_frequency(note: Note) -> f32 {
+    440.0 * 2.0f32.powf((note.as_u8() as f32 - 69.0) / 12.0)
+}
+
+fn time(name: &str, f: impl Fn(Note) -> f32) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for note in 0..=127 {
+            consume(f(Note::new(note)));
+        }
+    }
+    let elapsed = start.elapsed();
+    let per_call = elapsed.as_nanos() as f64 / (ROUNDS as f64 * 128.0);
+    println!(
+        "{:<8} {:>10.3?} total, {:.3}ns per note",
+        name, elapsed, per_call
+    );
+    elapsed
+}
+
+fn main() {
+    let table = time("table", |note| note.to_frequency());
+    let powf = time("powf", computed_frequency);
+    println!(
+        "table lookup is {:.1}x faster",
+        powf.as_secs_f64() / table.as_secs_f64()
+    );
+}
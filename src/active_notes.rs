@@ -0,0 +1,155 @@
+//! Tracking of held notes across all channels
+
+use crate::{Channel, MidiMessage, Note};
+
+const ALL_SOUND_OFF: u8 = 120;
+const ALL_NOTES_OFF: u8 = 123;
+
+/// The set of notes currently held on each channel
+///
+/// Every channel uses a fixed 128 bit set, so no allocation is needed. Note on messages with a
+/// velocity of 0 are treated as note off messages.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ActiveNotes {
+    channels: [u128; 16],
+}
+
+impl ActiveNotes {
+    /// Create a new `ActiveNotes` with no notes held
+    pub const fn new() -> Self {
+        Self { channels: [0; 16] }
+    }
+
+    /// Mark a note as held on a channel
+    pub fn on(&mut self, channel: Channel, note: Note) {
+        self.channels[channel.index() as usize] |= 1 << note.as_u8();
+    }
+
+    /// Mark a note as released on a channel
+    pub fn off(&mut self, channel: Channel, note: Note) {
+        self.channels[channel.index() as usize] &= !(1 << note.as_u8());
+    }
+
+    /// Check whether a note is held on a channel
+    pub const fn is_on(&self, channel: Channel, note: Note) -> bool {
+        self.channels[channel.index() as usize] & (1 << note.as_u8()) != 0
+    }
+
+    /// Check whether no notes are held on any channel
+    pub fn is_empty(&self) -> bool {
+        self.channels.iter().all(|notes| *notes == 0)
+    }
+
+    /// Release all notes on a channel
+    pub fn clear_channel(&mut self, channel: Channel) {
+        self.channels[channel.index() as usize] = 0;
+    }
+
+    /// Release all notes on every channel
+    pub fn clear(&mut self) {
+        self.channels = [0; 16];
+    }
+
+    /// Update the held notes from a message
+    ///
+    /// # Note
+    /// * Note on messages with a velocity of 0 release the note
+    /// * All sound off (control 120) and all notes off (control 123) release every note on their
+    ///   channel, a system reset releases every note on every channel
+    /// * Other messages are ignored
+    ///
+    pub fn feed(&mut self, message: &MidiMessage) {
+        match *message {
+            MidiMessage::NoteOn(channel, note, velocity) if velocity.as_u8() > 0 => {
+                self.on(channel, note)
+            }
+            MidiMessage::NoteOn(channel, note, _) | MidiMessage::NoteOff(channel, note, _) => {
+                self.off(channel, note)
+            }
+            MidiMessage::ControlChange(channel, control, _)
+                if control.as_u8() == ALL_SOUND_OFF || control.as_u8() == ALL_NOTES_OFF =>
+            {
+                self.clear_channel(channel)
+            }
+            MidiMessage::Reset => self.clear(),
+            _ => {}
+        }
+    }
+
+    /// Iterate over all held notes, ordered by channel and then by note number
+    pub fn iter_held(&self) -> impl Iterator<Item = (Channel, Note)> + '_ {
+        Channel::all().flat_map(move |channel| {
+            let notes = self.channels[channel.index() as usize];
+            (0..128u8)
+                .filter(move |note| notes & (1 << note) != 0)
+                .map(move |note| (channel, Note::new(note)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_note_on_and_off() {
+        let mut notes = ActiveNotes::new();
+        assert!(notes.is_empty());
+        notes.on(Channel::C1, Note::C3);
+        notes.on(Channel::C16, Note::G8);
+        assert!(notes.is_on(Channel::C1, Note::C3));
+        assert!(notes.is_on(Channel::C16, Note::G8));
+        assert!(!notes.is_on(Channel::C2, Note::C3));
+        notes.off(Channel::C1, Note::C3);
+        assert!(!notes.is_on(Channel::C1, Note::C3));
+        assert!(!notes.is_empty());
+    }
+
+    #[test]
+    fn feed_messages() {
+        let mut notes = ActiveNotes::new();
+        notes.feed(&MidiMessage::note_on(0, 60, 100));
+        notes.feed(&MidiMessage::note_on(0, 64, 100));
+        notes.feed(&MidiMessage::note_on(2, 67, 100));
+        notes.feed(&MidiMessage::control_change(0, 64, 127));
+        assert!(notes.is_on(Channel::C1, Note::new(64)));
+
+        notes.feed(&MidiMessage::note_on(0, 64, 0));
+        notes.feed(&MidiMessage::note_off(2, 67, 64));
+        assert!(!notes.is_on(Channel::C1, Note::new(64)));
+        assert!(!notes.is_on(Channel::C3, Note::new(67)));
+        assert!(notes.is_on(Channel::C1, Note::new(60)));
+    }
+
+    #[test]
+    fn release_notes_on_panic_messages() {
+        let mut notes = ActiveNotes::new();
+        notes.feed(&MidiMessage::note_on(0, 60, 100));
+        notes.feed(&MidiMessage::note_on(1, 60, 100));
+        notes.feed(&MidiMessage::all_notes_off(0));
+        assert!(!notes.is_on(Channel::C1, Note::new(60)));
+        assert!(notes.is_on(Channel::C2, Note::new(60)));
+
+        notes.feed(&MidiMessage::all_sound_off(1));
+        assert!(notes.is_empty());
+
+        notes.feed(&MidiMessage::note_on(0, 60, 100));
+        notes.feed(&MidiMessage::note_on(15, 60, 100));
+        notes.feed(&MidiMessage::Reset);
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn iterate_held_notes() {
+        let mut notes = ActiveNotes::new();
+        notes.on(Channel::C3, Note::new(0));
+        notes.on(Channel::C1, Note::new(127));
+        notes.on(Channel::C1, Note::new(60));
+        let mut held = notes.iter_held();
+        assert_eq!(Some((Channel::C1, Note::new(60))), held.next());
+        assert_eq!(Some((Channel::C1, Note::new(127))), held.next());
+        assert_eq!(Some((Channel::C3, Note::new(0))), held.next());
+        assert_eq!(None, held.next());
+    }
+}
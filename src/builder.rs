@@ -0,0 +1,148 @@
+//! A builder for channel voice messages
+
+use crate::{Channel, Control, MidiMessage, Note, Program, Value14, Value7};
+
+/// Builds channel voice messages one field at a time
+///
+/// Start with `on` for the channel, set the fields the message needs and finish with the method
+/// for the message type, for example `ChannelMessageBuilder::on(Channel::C1).note(Note::C4)
+/// .velocity(100).note_on()`. Fields that are not set keep their initial values, the note is 60
+/// and the velocity or value, control and program are 0.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelMessageBuilder {
+    channel: Channel,
+    note: Note,
+    control: Control,
+    program: Program,
+    value: Value7,
+}
+
+impl ChannelMessageBuilder {
+    /// Start building a message on `channel`
+    pub const fn on(channel: Channel) -> Self {
+        Self {
+            channel,
+            note: Note::C3,
+            control: Control::new(0),
+            program: Program::new(0),
+            value: Value7::MIN,
+        }
+    }
+
+    /// Set the note of a note on, note off or key pressure message
+    pub fn note(self, note: impl Into<Note>) -> Self {
+        Self {
+            note: note.into(),
+            ..self
+        }
+    }
+
+    /// Set the velocity of a note on or note off message, this is the same field as `value`
+    pub fn velocity(self, velocity: impl Into<Value7>) -> Self {
+        self.value(velocity)
+    }
+
+    /// Set the value of a control change message or the pressure of a key pressure or channel
+    /// pressure message
+    pub fn value(self, value: impl Into<Value7>) -> Self {
+        Self {
+            value: value.into(),
+            ..self
+        }
+    }
+
+    /// Set the controller of a control change message
+    pub fn control(self, control: impl Into<Control>) -> Self {
+        Self {
+            control: control.into(),
+            ..self
+        }
+    }
+
+    /// Set the program of a program change message
+    pub fn program(self, program: impl Into<Program>) -> Self {
+        Self {
+            program: program.into(),
+            ..self
+        }
+    }
+
+    /// Build a note on message from the channel, note and velocity
+    pub fn note_on(self) -> MidiMessage {
+        MidiMessage::NoteOn(self.channel, self.note, self.value)
+    }
+
+    /// Build a note off message from the channel, note and velocity
+    pub fn note_off(self) -> MidiMessage {
+        MidiMessage::NoteOff(self.channel, self.note, self.value)
+    }
+
+    /// Build a key pressure message from the channel, note and value
+    pub fn key_pressure(self) -> MidiMessage {
+        MidiMessage::KeyPressure(self.channel, self.note, self.value)
+    }
+
+    /// Build a control change message from the channel, control and value
+    pub fn control_change(self) -> MidiMessage {
+        MidiMessage::ControlChange(self.channel, self.control, self.value)
+    }
+
+    /// Build a program change message from the channel and program
+    pub fn program_change(self) -> MidiMessage {
+        MidiMessage::ProgramChange(self.channel, self.program)
+    }
+
+    /// Build a channel pressure message from the channel and value
+    pub fn channel_pressure(self) -> MidiMessage {
+        MidiMessage::ChannelPressure(self.channel, self.value)
+    }
+
+    /// Build a pitch bend change message on the channel
+    pub fn pitch_bend(self, value: Value14) -> MidiMessage {
+        MidiMessage::PitchBendChange(self.channel, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_note_messages() {
+        let builder = ChannelMessageBuilder::on(Channel::C2).note(Note::C4);
+        assert_eq!(
+            MidiMessage::NoteOn(Channel::C2, Note::C4, Value7::new(100)),
+            builder.velocity(100).note_on()
+        );
+        assert_eq!(
+            MidiMessage::NoteOff(Channel::C2, Note::C4, Value7::new(0)),
+            builder.note_off()
+        );
+        assert_eq!(
+            MidiMessage::KeyPressure(Channel::C2, Note::new(61), Value7::new(5)),
+            builder.note(61).value(5).key_pressure()
+        );
+    }
+
+    #[test]
+    fn build_channel_messages() {
+        let builder = ChannelMessageBuilder::on(Channel::C16);
+        assert_eq!(
+            MidiMessage::control_change(15, 7, 100),
+            builder.control(7).value(100).control_change()
+        );
+        assert_eq!(
+            MidiMessage::program_change(15, 12),
+            builder.program(12).program_change()
+        );
+        assert_eq!(
+            MidiMessage::ChannelPressure(Channel::C16, Value7::new(3)),
+            builder.value(3).channel_pressure()
+        );
+        assert_eq!(
+            MidiMessage::pitch_bend(15, Value14::MAX),
+            builder.pitch_bend(Value14::MAX)
+        );
+    }
+}
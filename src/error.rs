@@ -0,0 +1,75 @@
+//! The error type returned by fallible constructors and conversions
+
+use crate::ParseNoteError;
+use core::fmt;
+
+/// The error returned by the crate's fallible constructors, conversions and parsers
+///
+/// Implements `core::error::Error` with the optional `core-error` feature, which needs Rust 1.81
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MidiError {
+    /// A value is outside of the valid range for the type it is converted to
+    ValueOutOfRange,
+    /// The bytes end before the message is complete
+    UnexpectedEnd,
+    /// The status byte is missing, undefined or not supported
+    InvalidStatus,
+    /// A note could not be parsed from a string
+    InvalidNote(ParseNoteError),
+}
+
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ValueOutOfRange => f.write_str("value out of range"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of message"),
+            Self::InvalidStatus => f.write_str("invalid status byte"),
+            Self::InvalidNote(err) => write!(f, "invalid note: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl core::error::Error for MidiError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidNote(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl core::error::Error for ParseNoteError {}
+
+impl From<ParseNoteError> for MidiError {
+    fn from(err: ParseNoteError) -> Self {
+        Self::InvalidNote(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn display_errors() {
+        assert_eq!("value out of range", MidiError::ValueOutOfRange.to_string());
+        assert_eq!(
+            "invalid note: invalid octave",
+            MidiError::from(ParseNoteError::InvalidOctave).to_string()
+        );
+    }
+
+    #[cfg(feature = "core-error")]
+    #[test]
+    fn error_source() {
+        use core::error::Error;
+        assert!(MidiError::InvalidStatus.source().is_none());
+        let err = MidiError::InvalidNote(ParseNoteError::InvalidNoteName);
+        assert_eq!("invalid note name", err.source().unwrap().to_string());
+    }
+}
@@ -1,7 +1,39 @@
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Asserts that a value passed to a constructor or conversion is in range
+///
+/// Out of range values are clamped, so by default this is a `debug_assert!` that only panics in
+/// debug builds. With the `strict` feature enabled this is an `assert!` that panics in release
+/// builds as well.
+macro_rules! range_assert {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "strict") {
+            assert!($($arg)*);
+        } else {
+            debug_assert!($($arg)*);
+        }
+    };
+}
+
+mod active_notes;
+mod builder;
+mod error;
+#[cfg(feature = "gm-names")]
+mod gm;
 #[warn(missing_debug_implementations, missing_docs)]
 mod message;
 mod note;
+mod parameter;
+mod sysex;
+mod timecode;
 
+pub use active_notes::*;
+pub use builder::*;
+pub use error::*;
 pub use message::*;
 pub use note::*;
+pub use parameter::*;
+pub use sysex::*;
+pub use timecode::*;
@@ -1,9 +1,13 @@
 //! This module contains data types to represent the different messages that can be sent over MIDI.
 
-use crate::Note;
+use crate::{MidiError, Note};
+use core::fmt;
 
 /// An enum with variants for all possible Midi messages.
-#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+///
+/// A message takes at most 4 bytes, the discriminant plus up to three data bytes. This is
+/// checked by the tests so queues of messages stay compact on small targets.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum MidiMessage {
     // Channel voice messages
@@ -96,6 +100,642 @@ impl MidiMessage {
             | Self::Reset => 1,
         }
     }
+
+    /// The channel of a channel voice message, `None` for system messages
+    pub const fn channel(&self) -> Option<Channel> {
+        match self {
+            Self::NoteOff(channel, ..)
+            | Self::NoteOn(channel, ..)
+            | Self::KeyPressure(channel, ..)
+            | Self::ControlChange(channel, ..)
+            | Self::ProgramChange(channel, ..)
+            | Self::ChannelPressure(channel, ..)
+            | Self::PitchBendChange(channel, ..) => Some(*channel),
+            _ => None,
+        }
+    }
+
+    /// The 0 based channel index of a channel voice message, `None` for system messages
+    ///
+    /// # Note
+    /// * Handy as a key for sorting or grouping messages by channel, `None` sorts before every
+    ///   channel so system messages come first
+    ///
+    pub const fn channel_index(&self) -> Option<u8> {
+        match self.channel() {
+            Some(channel) => Some(channel.index()),
+            None => None,
+        }
+    }
+
+    /// The note of a note on, note off or key pressure message, `None` for other messages
+    pub const fn note(&self) -> Option<Note> {
+        match self {
+            Self::NoteOff(_, note, _)
+            | Self::NoteOn(_, note, _)
+            | Self::KeyPressure(_, note, _) => Some(*note),
+            _ => None,
+        }
+    }
+
+    /// The velocity of a note on or note off message, or the pressure of a key pressure or
+    /// channel pressure message. `None` for other messages
+    pub const fn velocity(&self) -> Option<Value7> {
+        match self {
+            Self::NoteOff(_, _, value)
+            | Self::NoteOn(_, _, value)
+            | Self::KeyPressure(_, _, value)
+            | Self::ChannelPressure(_, value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The controller of a control change message, `None` for other messages
+    pub const fn control(&self) -> Option<Control> {
+        match self {
+            Self::ControlChange(_, control, _) => Some(*control),
+            _ => None,
+        }
+    }
+
+    /// The value of a control change message, `None` for other messages
+    pub const fn control_value(&self) -> Option<Value7> {
+        match self {
+            Self::ControlChange(_, _, value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The program of a program change message, `None` for other messages
+    pub const fn program(&self) -> Option<Program> {
+        match self {
+            Self::ProgramChange(_, program) => Some(*program),
+            _ => None,
+        }
+    }
+
+    /// The program number of a program change message as a `u8`, `None` for other messages
+    pub const fn program_value(&self) -> Option<u8> {
+        match self {
+            Self::ProgramChange(_, program) => Some(program.0),
+            _ => None,
+        }
+    }
+
+    /// The value of a pitch bend change message, `None` for other messages
+    ///
+    /// # Note
+    /// * Named `pitch_bend_value` as `pitch_bend` is the constructor for pitch bend messages
+    ///
+    pub const fn pitch_bend_value(&self) -> Option<Value14> {
+        match self {
+            Self::PitchBendChange(_, value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value of a pitch bend change message in the -1.0..1.0 range, `None` for other messages
+    pub fn pitch_bend_f32(&self) -> Option<f32> {
+        self.pitch_bend_value().map(f32::from)
+    }
+
+    /// The pressure of a channel pressure message in the 0.0..1.0 range, `None` for other
+    /// messages
+    pub fn channel_pressure_f32(&self) -> Option<f32> {
+        match self {
+            Self::ChannelPressure(_, value) => Some(f32::from(*value)),
+            _ => None,
+        }
+    }
+
+    /// Render the message into a newly allocated `Vec` of exactly `len()` bytes
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> alloc::vec::Vec<u8> {
+        self.padded_bytes()[..self.len()].to_vec()
+    }
+
+    /// Render the message into a fixed capacity `heapless::Vec` of exactly `len()` bytes
+    #[cfg(feature = "heapless")]
+    pub fn to_heapless(&self) -> heapless::Vec<u8, 3> {
+        heapless::Vec::from_slice(&self.padded_bytes()[..self.len()])
+            .expect("messages are at most 3 bytes long")
+    }
+
+    /// The status byte of the message, including the channel for channel voice messages
+    pub const fn status_byte(&self) -> u8 {
+        self.padded_bytes()[0]
+    }
+
+    /// Iterate over the rendered bytes of the message, yielding exactly `len()` bytes
+    pub fn bytes(&self) -> impl ExactSizeIterator<Item = u8> {
+        self.to_midi_bytes().into_iter()
+    }
+
+    /// Render the message into an owned `MidiBytes` of exactly `len()` bytes
+    pub fn to_midi_bytes(&self) -> MidiBytes {
+        MidiBytes {
+            bytes: self.padded_bytes(),
+            len: self.len() as u8,
+        }
+    }
+
+    /// Render the message into an array padded with zeros to the maximum message length
+    const fn padded_bytes(&self) -> [u8; 3] {
+        match *self {
+            Self::NoteOff(channel, note, velocity) => {
+                [status::NOTE_OFF | channel.0, note.as_u8(), velocity.0]
+            }
+            Self::NoteOn(channel, note, velocity) => {
+                [status::NOTE_ON | channel.0, note.as_u8(), velocity.0]
+            }
+            Self::KeyPressure(channel, note, value) => {
+                [status::KEY_PRESSURE | channel.0, note.as_u8(), value.0]
+            }
+            Self::ControlChange(channel, control, value) => {
+                [status::CONTROL_CHANGE | channel.0, control.0, value.0]
+            }
+            Self::ProgramChange(channel, program) => {
+                [status::PROGRAM_CHANGE | channel.0, program.0, 0]
+            }
+            Self::ChannelPressure(channel, value) => {
+                [status::CHANNEL_PRESSURE | channel.0, value.0, 0]
+            }
+            Self::PitchBendChange(channel, value) => {
+                [status::PITCH_BEND_CHANGE | channel.0, value.1, value.0]
+            }
+            Self::QuarterFrame(frame) => [status::QUARTER_FRAME, frame.0, 0],
+            Self::SongPositionPointer(value) => [status::SONG_POSITION_POINTER, value.1, value.0],
+            Self::SongSelect(value) => [status::SONG_SELECT, value.0, 0],
+            Self::TuneRequest => [status::TUNE_REQUEST, 0, 0],
+            Self::TimingClock => [status::TIMING_CLOCK, 0, 0],
+            Self::Start => [status::START, 0, 0],
+            Self::Continue => [status::CONTINUE, 0, 0],
+            Self::Stop => [status::STOP, 0, 0],
+            Self::ActiveSensing => [status::ACTIVE_SENSING, 0, 0],
+            Self::Reset => [status::RESET, 0, 0],
+        }
+    }
+
+    /// The length of a message including the status, looked up from its status byte
+    ///
+    /// # Note
+    /// * Returns `None` for data bytes, system exclusive and undefined status bytes
+    ///
+    pub const fn message_len_from_status(status: u8) -> Option<usize> {
+        match status & 0xF0 {
+            status::NOTE_OFF
+            | status::NOTE_ON
+            | status::KEY_PRESSURE
+            | status::CONTROL_CHANGE
+            | status::PITCH_BEND_CHANGE => Some(3),
+            status::PROGRAM_CHANGE | status::CHANNEL_PRESSURE => Some(2),
+            0xF0 => match status {
+                status::SONG_POSITION_POINTER => Some(3),
+                status::QUARTER_FRAME | status::SONG_SELECT => Some(2),
+                status::TUNE_REQUEST
+                | status::TIMING_CLOCK
+                | status::START
+                | status::CONTINUE
+                | status::STOP
+                | status::ACTIVE_SENSING
+                | status::RESET => Some(1),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The number of data bytes that follow a status byte, 0, 1 or 2
+    ///
+    /// # Note
+    /// * Returns `None` for data bytes, system exclusive and undefined status bytes
+    ///
+    pub const fn data_bytes_for_status(status: u8) -> Option<u8> {
+        match Self::message_len_from_status(status) {
+            Some(len) => Some(len as u8 - 1),
+            None => None,
+        }
+    }
+
+    /// Create a message from a complete status byte and up to two data bytes
+    ///
+    /// # Arguments
+    /// * `status` - the status byte, running status is not supported
+    /// * `d1` - the first data byte, ignored for messages without data
+    /// * `d2` - the second data byte, ignored for messages with less than two data bytes
+    ///
+    /// # Note
+    /// * Returns `None` for system exclusive, undefined and data status bytes, or when a data byte
+    ///   that is used has its msb set
+    ///
+    pub fn from_status_and_data(status: u8, d1: u8, d2: u8) -> Option<MidiMessage> {
+        let len = Self::message_len_from_status(status)?;
+        if (len > 1 && d1 > 127) || (len > 2 && d2 > 127) {
+            return None;
+        }
+        let channel = Channel(status & 0x0F);
+        Some(match status & 0xF0 {
+            status::NOTE_OFF => Self::NoteOff(channel, Note::new(d1), Value7(d2)),
+            status::NOTE_ON => Self::NoteOn(channel, Note::new(d1), Value7(d2)),
+            status::KEY_PRESSURE => Self::KeyPressure(channel, Note::new(d1), Value7(d2)),
+            status::CONTROL_CHANGE => Self::ControlChange(channel, Control(d1), Value7(d2)),
+            status::PROGRAM_CHANGE => Self::ProgramChange(channel, Program(d1)),
+            status::CHANNEL_PRESSURE => Self::ChannelPressure(channel, Value7(d1)),
+            status::PITCH_BEND_CHANGE => Self::PitchBendChange(channel, Value14(d2, d1)),
+            _ => match status {
+                status::QUARTER_FRAME => Self::QuarterFrame(QuarterFrame(d1)),
+                status::SONG_POSITION_POINTER => Self::SongPositionPointer(Value14(d2, d1)),
+                status::SONG_SELECT => Self::SongSelect(Value7(d1)),
+                status::TUNE_REQUEST => Self::TuneRequest,
+                status::TIMING_CLOCK => Self::TimingClock,
+                status::START => Self::Start,
+                status::CONTINUE => Self::Continue,
+                status::STOP => Self::Stop,
+                status::ACTIVE_SENSING => Self::ActiveSensing,
+                _ => Self::Reset,
+            },
+        })
+    }
+
+    /// Create a note on message from plain integers, clamping each to its valid range
+    pub const fn note_on(channel: u8, note: u8, velocity: u8) -> Self {
+        Self::NoteOn(
+            Channel::new(channel),
+            Note::new(note),
+            Value7::new(velocity),
+        )
+    }
+
+    /// Create a note off message from plain integers, clamping each to its valid range
+    pub const fn note_off(channel: u8, note: u8, velocity: u8) -> Self {
+        Self::NoteOff(
+            Channel::new(channel),
+            Note::new(note),
+            Value7::new(velocity),
+        )
+    }
+
+    /// Create a control change message from plain integers, clamping each to its valid range
+    pub const fn control_change(channel: u8, control: u8, value: u8) -> Self {
+        Self::ControlChange(
+            Channel::new(channel),
+            Control::new(control),
+            Value7::new(value),
+        )
+    }
+
+    /// Create a program change message from plain integers, clamping each to its valid range
+    pub const fn program_change(channel: u8, program: u8) -> Self {
+        Self::ProgramChange(Channel::new(channel), Program::new(program))
+    }
+
+    /// Create a pitch bend message, clamping the channel to its valid range
+    pub const fn pitch_bend(channel: u8, value: Value14) -> Self {
+        Self::PitchBendChange(Channel::new(channel), value)
+    }
+
+    /// Create the bank select and program change messages needed to select a patch
+    ///
+    /// # Note
+    /// * The messages are returned in transmit order, the bank MSB on control 0, the bank LSB on
+    ///   control 32 and then the program change
+    /// * Like the other message constructors `channel` is the 0 based channel, clamped to 0..15
+    ///
+    pub const fn bank_select(channel: u8, bank: Value14, program: Program) -> [Self; 3] {
+        let channel = Channel::new(channel);
+        [
+            Self::ControlChange(channel, Control(0), Value7(bank.0)),
+            Self::ControlChange(channel, Control(32), Value7(bank.1)),
+            Self::ProgramChange(channel, program),
+        ]
+    }
+
+    /// Create an All Sound Off channel mode message, control 120
+    pub const fn all_sound_off(channel: u8) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(120), Value7(0))
+    }
+
+    /// Create a Reset All Controllers channel mode message, control 121
+    pub const fn reset_all_controllers(channel: u8) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(121), Value7(0))
+    }
+
+    /// Create a Local Control On or Off channel mode message, control 122
+    pub const fn local_control(channel: u8, on: bool) -> Self {
+        Self::ControlChange(
+            Channel::new(channel),
+            Control(122),
+            Value7(if on { 127 } else { 0 }),
+        )
+    }
+
+    /// Create an All Notes Off channel mode message, control 123
+    pub const fn all_notes_off(channel: u8) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(123), Value7(0))
+    }
+
+    /// Create an Omni Mode Off channel mode message, control 124
+    pub const fn omni_off(channel: u8) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(124), Value7(0))
+    }
+
+    /// Create an Omni Mode On channel mode message, control 125
+    pub const fn omni_on(channel: u8) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(125), Value7(0))
+    }
+
+    /// Create a Mono Mode On channel mode message, control 126
+    ///
+    /// # Arguments
+    /// * `channel` - the 0 based basic channel of the receiver
+    /// * `channels` - the number of mono channels, or 0 to use as many as the receiver supports
+    ///
+    pub const fn mono_on(channel: u8, channels: Value7) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(126), channels)
+    }
+
+    /// Create a Poly Mode On channel mode message, control 127
+    pub const fn poly_on(channel: u8) -> Self {
+        Self::ControlChange(Channel::new(channel), Control(127), Value7(0))
+    }
+
+    /// Returns true for channel mode messages, these are control changes on controllers 120..127
+    pub const fn is_channel_mode(&self) -> bool {
+        matches!(self, Self::ControlChange(_, Control(120..=127), _))
+    }
+
+    /// Returns true when both messages are note on or note off messages for the same channel and
+    /// note, regardless of whether they are on or off and of their velocity
+    pub const fn matches_note(&self, other: &MidiMessage) -> bool {
+        match other {
+            Self::NoteOn(channel, note, _) | Self::NoteOff(channel, note, _) => {
+                self.is_note_event_for(*channel, *note)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true when this message turns off `note` on `channel`, this includes note on
+    /// messages with a velocity of 0
+    pub const fn is_note_off_for(&self, channel: Channel, note: Note) -> bool {
+        self.is_note_off_like() && self.is_note_event_for(channel, note)
+    }
+
+    /// Returns true for note on and note off messages for `note` on `channel`
+    const fn is_note_event_for(&self, channel: Channel, note: Note) -> bool {
+        match self {
+            Self::NoteOn(c, n, _) | Self::NoteOff(c, n, _) => {
+                c.0 == channel.0 && n.as_u8() == note.as_u8()
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true when this message affects the voice playing `note` on `channel`
+    ///
+    /// # Note
+    /// * Note on, note off and key pressure messages only affect the voice for their own note
+    /// * Control change, channel pressure and pitch bend messages affect every voice on their
+    ///   channel
+    /// * Program changes and system messages never affect a voice that is already playing
+    ///
+    pub const fn affects(&self, channel: Channel, note: Note) -> bool {
+        match self {
+            Self::NoteOn(c, n, _) | Self::NoteOff(c, n, _) | Self::KeyPressure(c, n, _) => {
+                c.0 == channel.0 && n.as_u8() == note.as_u8()
+            }
+            Self::ControlChange(c, ..)
+            | Self::ChannelPressure(c, _)
+            | Self::PitchBendChange(c, _) => c.0 == channel.0,
+            _ => false,
+        }
+    }
+
+    /// Replace the channel of a channel voice message using `f`, system messages are returned
+    /// unchanged
+    pub fn map_channel(self, f: impl FnOnce(Channel) -> Channel) -> Self {
+        match self {
+            Self::NoteOff(channel, note, velocity) => Self::NoteOff(f(channel), note, velocity),
+            Self::NoteOn(channel, note, velocity) => Self::NoteOn(f(channel), note, velocity),
+            Self::KeyPressure(channel, note, value) => Self::KeyPressure(f(channel), note, value),
+            Self::ControlChange(channel, control, value) => {
+                Self::ControlChange(f(channel), control, value)
+            }
+            Self::ProgramChange(channel, program) => Self::ProgramChange(f(channel), program),
+            Self::ChannelPressure(channel, value) => Self::ChannelPressure(f(channel), value),
+            Self::PitchBendChange(channel, value) => Self::PitchBendChange(f(channel), value),
+            message => message,
+        }
+    }
+
+    /// Replace the channel of a channel voice message, system messages are returned unchanged
+    pub fn with_channel(self, channel: Channel) -> Self {
+        self.map_channel(|_| channel)
+    }
+
+    /// Transpose the note of note on, note off and key pressure messages by a number of
+    /// semitones, other messages are returned unchanged
+    ///
+    /// # Note
+    /// * Notes are clamped at the edges of the 0..127 range
+    ///
+    pub fn transpose(self, semitones: i8) -> Self {
+        match self {
+            Self::NoteOff(channel, note, velocity) => {
+                Self::NoteOff(channel, note + semitones, velocity)
+            }
+            Self::NoteOn(channel, note, velocity) => {
+                Self::NoteOn(channel, note + semitones, velocity)
+            }
+            Self::KeyPressure(channel, note, value) => {
+                Self::KeyPressure(channel, note + semitones, value)
+            }
+            _ => self,
+        }
+    }
+
+    /// A sort key for putting messages that share a timestamp into a sensible transmit order,
+    /// lower values should be sent first
+    ///
+    /// The order is:
+    /// 0. System real time messages, these are timing critical
+    /// 1. System common messages
+    /// 2. Control changes, so bank selects and controller state are set before the program
+    ///    change and notes that depend on them
+    /// 3. Program changes
+    /// 4. Pitch bend and channel pressure
+    /// 5. Note offs, including note ons with a velocity of 0, so a note is released before it is
+    ///    retriggered
+    /// 6. Note ons
+    /// 7. Key pressure, which applies to notes that are already on
+    ///
+    /// # Note
+    /// * Use a stable sort like `slice::sort_by_key` to keep the order of messages with the same
+    ///   priority, for example a bank select MSB and LSB
+    ///
+    pub const fn transmit_priority(&self) -> u8 {
+        match self {
+            Self::TimingClock
+            | Self::Start
+            | Self::Continue
+            | Self::Stop
+            | Self::ActiveSensing
+            | Self::Reset => 0,
+            Self::QuarterFrame(..)
+            | Self::SongPositionPointer(..)
+            | Self::SongSelect(..)
+            | Self::TuneRequest => 1,
+            Self::ControlChange(..) => 2,
+            Self::ProgramChange(..) => 3,
+            Self::PitchBendChange(..) | Self::ChannelPressure(..) => 4,
+            Self::NoteOff(..) | Self::NoteOn(_, _, Value7(0)) => 5,
+            Self::NoteOn(..) => 6,
+            Self::KeyPressure(..) => 7,
+        }
+    }
+
+    /// Returns true for note off messages and for note on messages with a velocity of 0, which
+    /// are treated as note off by convention
+    pub const fn is_note_off_like(&self) -> bool {
+        matches!(self, Self::NoteOff(..) | Self::NoteOn(_, _, Value7(0)))
+    }
+
+    /// Rewrite a note on message with a velocity of 0 into the equivalent note off message
+    ///
+    /// # Note
+    /// * The note off message gets a release velocity of 64, as recommended by the MIDI spec for
+    ///   devices without release velocity
+    /// * All other messages are returned unchanged
+    ///
+    pub fn normalize_note_off(self) -> MidiMessage {
+        match self {
+            Self::NoteOn(channel, note, Value7(0)) => Self::NoteOff(channel, note, Value7::CENTER),
+            message => message,
+        }
+    }
+
+    /// Compare two messages, treating every way of turning off a note as equal
+    ///
+    /// # Note
+    /// * A note on with a velocity of 0 equals a note off for the same channel and note, and the
+    ///   release velocity of note off messages is ignored
+    /// * All other messages are compared exactly, like `==`
+    ///
+    pub fn semantically_eq(&self, other: &MidiMessage) -> bool {
+        if self.is_note_off_like() && other.is_note_off_like() {
+            self.channel() == other.channel() && self.note() == other.note()
+        } else {
+            self == other
+        }
+    }
+
+    /// Split a 14 bit value into the two control change messages needed to transmit it
+    ///
+    /// # Arguments
+    /// * `channel` - the channel to send the messages on
+    /// * `control` - the MSB controller number, in the 0..31 range
+    /// * `value` - the 14 bit value
+    ///
+    /// # Note
+    /// * The messages are returned in transmit order, MSB first followed by the LSB on controller
+    ///   `control + 32`
+    /// * Returns `None` when `control` is not the MSB of a 14 bit controller pair
+    ///
+    pub const fn control_change_14bit(
+        channel: Channel,
+        control: Control,
+        value: Value14,
+    ) -> Option<[MidiMessage; 2]> {
+        if control.0 > 31 {
+            return None;
+        }
+        Some([
+            Self::ControlChange(channel, control, Value7(value.0)),
+            Self::ControlChange(channel, Control(control.0 + 32), Value7(value.1)),
+        ])
+    }
+
+    /// Combine an MSB and LSB control change message pair into a single 14 bit value
+    ///
+    /// # Note
+    /// * Returns `None` when the messages are not control changes on the same channel, or when
+    ///   their controllers are not a matching MSB (0..31) and LSB (32..63) pair
+    ///
+    pub fn combine_control_change_14bit(msb: MidiMessage, lsb: MidiMessage) -> Option<Value14> {
+        match (msb, lsb) {
+            (
+                Self::ControlChange(msb_channel, msb_control, msb_value),
+                Self::ControlChange(lsb_channel, lsb_control, lsb_value),
+            ) if msb_channel == lsb_channel
+                && msb_control.0 <= 31
+                && lsb_control.0 == msb_control.0 + 32 =>
+            {
+                Some(Value14(msb_value.0, lsb_value.0))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a complete message from the start of a byte slice
+///
+/// # Note
+/// * Bytes after the end of the message are ignored, running status is not supported
+/// * Returns `MidiError::InvalidStatus` for system exclusive, undefined and data status bytes,
+///   `MidiError::UnexpectedEnd` when the slice is too short and `MidiError::ValueOutOfRange` when
+///   a data byte has its msb set
+///
+impl TryFrom<&[u8]> for MidiMessage {
+    type Error = MidiError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, MidiError> {
+        let status = *bytes.first().ok_or(MidiError::UnexpectedEnd)?;
+        let len = Self::message_len_from_status(status).ok_or(MidiError::InvalidStatus)?;
+        let data = bytes.get(1..len).ok_or(MidiError::UnexpectedEnd)?;
+        let d1 = data.first().copied().unwrap_or(0);
+        let d2 = data.get(1).copied().unwrap_or(0);
+        Self::from_status_and_data(status, d1, d2).ok_or(MidiError::ValueOutOfRange)
+    }
+}
+
+/// The rendered bytes of a single message, see `MidiMessage::to_midi_bytes`
+///
+/// Owns the bytes in a fixed array sized for the longest message, so it can be stored without
+/// borrowing the message or allocating.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MidiBytes {
+    bytes: [u8; 3],
+    len: u8,
+}
+
+impl MidiBytes {
+    /// The number of bytes in the message, at most 3
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// The bytes of the message
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len()]
+    }
+}
+
+impl AsRef<[u8]> for MidiBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl IntoIterator for MidiBytes {
+    type Item = u8;
+    type IntoIter = core::iter::Take<core::array::IntoIter<u8, 3>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        self.bytes.into_iter().take(len)
+    }
 }
 
 #[allow(missing_docs)]
@@ -125,24 +765,69 @@ pub mod status {
 
 /// Represents a Midi channel, Midi channels can range from 0 to 15, but are represented as 1 based
 /// values Channel 1 to 16
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Channel(u8);
 
 impl Channel {
-    /// Create a new `Channel`
+    /// Create a new `Channel` from its 0 based index as sent over the wire
     ///
     /// # Arguments
-    /// * `channel` - the 0 based channel value
+    /// * `channel` - the 0 based channel value, so `new(0)` is channel 1
     ///
     /// # Note
     /// * The `channel` will be clamped so it is in the 0..15 valid range.
+    /// * Use `from_number` to create a channel from the 1 based number musicians use
     ///
     pub const fn new(channel: u8) -> Self {
-        debug_assert!(channel <= 15, "Channel exceeds valid range");
+        range_assert!(channel <= 15, "Channel exceeds valid range");
         Self(if channel > 15 { 15 } else { channel })
     }
 
+    /// Create a new `Channel`, rejecting out of range values instead of clamping them
+    ///
+    /// # Arguments
+    /// * `channel` - the value, must be in the 0..15 range
+    ///
+    pub const fn try_new(channel: u8) -> Result<Self, MidiError> {
+        if channel > 15 {
+            Err(MidiError::ValueOutOfRange)
+        } else {
+            Ok(Self(channel))
+        }
+    }
+
+    /// Get the 0 based channel value as a `u8`
+    pub const fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// The 0 based channel index as sent over the wire, in the 0..15 range
+    pub const fn index(&self) -> u8 {
+        self.0
+    }
+
+    /// The 1 based channel number as presented to musicians, in the 1..16 range
+    pub const fn number(&self) -> u8 {
+        self.0 + 1
+    }
+
+    /// Create a new `Channel` from its 1 based number as presented to musicians
+    ///
+    /// # Arguments
+    /// * `number` - the 1 based channel number, so `from_number(1)` is channel 1 with index 0
+    ///
+    /// # Note
+    /// * The `number` will be clamped so it is in the 1..16 valid range.
+    ///
+    pub const fn from_number(number: u8) -> Self {
+        range_assert!(
+            number >= 1 && number <= 16,
+            "Channel number exceeds valid range"
+        );
+        Self::new(if number == 0 { 0 } else { number - 1 })
+    }
+
     /// MIDI channel 1
     pub const C1: Self = Self::new(0);
     /// MIDI channel 2
@@ -180,6 +865,25 @@ impl Channel {
     pub const MIN: Self = Self::C1;
     /// The maximum MIDI channel
     pub const MAX: Self = Self::C16;
+
+    /// Iterate over all sixteen MIDI channels, from channel 1 to channel 16
+    pub fn all() -> impl ExactSizeIterator<Item = Channel> + DoubleEndedIterator {
+        (0..16).map(Self)
+    }
+}
+
+/// Formats the 1 based channel number, so `Channel::C1` is shown as "1"
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Defaults to channel 1
+impl Default for Channel {
+    fn default() -> Self {
+        Self::C1
+    }
 }
 
 impl From<u8> for Channel {
@@ -194,8 +898,84 @@ impl From<Channel> for u8 {
     }
 }
 
+/// A set of Midi channels stored as a bitmask, bit 0 is channel 1 and bit 15 is channel 16
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelMask(u16);
+
+impl ChannelMask {
+    /// Create a `ChannelMask` containing all sixteen channels
+    pub const fn all() -> Self {
+        Self(0xFFFF)
+    }
+
+    /// Create an empty `ChannelMask`
+    pub const fn none() -> Self {
+        Self(0)
+    }
+
+    /// Add a channel to the set
+    pub fn insert(&mut self, channel: Channel) {
+        self.0 |= 1 << channel.0;
+    }
+
+    /// Remove a channel from the set
+    pub fn remove(&mut self, channel: Channel) {
+        self.0 &= !(1 << channel.0);
+    }
+
+    /// Returns a copy of the set with a channel added, this can be used to build masks in const
+    /// contexts
+    pub const fn with(self, channel: Channel) -> Self {
+        Self(self.0 | 1 << channel.0)
+    }
+
+    /// Returns true if the channel is in the set
+    pub const fn contains(&self, channel: Channel) -> bool {
+        self.0 & (1 << channel.0) != 0
+    }
+
+    /// Returns true if the set contains no channels
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the channels in the set, from channel 1 to channel 16
+    pub fn iter(&self) -> impl Iterator<Item = Channel> {
+        let mask = *self;
+        Channel::all().filter(move |channel| mask.contains(*channel))
+    }
+
+    /// Get the bitmask as a `u16`
+    pub const fn as_u16(&self) -> u16 {
+        self.0
+    }
+}
+
+impl From<u16> for ChannelMask {
+    fn from(mask: u16) -> Self {
+        Self(mask)
+    }
+}
+
+impl From<ChannelMask> for u16 {
+    fn from(mask: ChannelMask) -> u16 {
+        mask.0
+    }
+}
+
+impl FromIterator<Channel> for ChannelMask {
+    fn from_iter<I: IntoIterator<Item = Channel>>(channels: I) -> Self {
+        let mut mask = Self::none();
+        for channel in channels {
+            mask.insert(channel);
+        }
+        mask
+    }
+}
+
 /// A Midi controller number
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Control(u8);
 
@@ -209,9 +989,49 @@ impl Control {
     /// * The `control` number will be clamped so it is in the 0..127 valid range
     ///
     pub const fn new(control: u8) -> Self {
-        debug_assert!(control < 127, "Control exceeds valid range");
+        range_assert!(control <= 127, "Control exceeds valid range");
         Self(if control > 127 { 127 } else { control })
     }
+
+    /// Create a new `Control`, rejecting out of range values instead of clamping them
+    ///
+    /// # Arguments
+    /// * `control` - the value, must be in the 0..127 range
+    ///
+    pub const fn try_new(control: u8) -> Result<Self, MidiError> {
+        if control > 127 {
+            Err(MidiError::ValueOutOfRange)
+        } else {
+            Ok(Self(control))
+        }
+    }
+
+    /// Get the control number value as a `u8`
+    pub const fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns true for controllers 0..31, which carry the MSB of a 14 bit value
+    pub const fn is_msb_of_pair(&self) -> bool {
+        self.0 <= 31
+    }
+
+    /// Returns true for controllers 32..63, which carry the LSB of a 14 bit value
+    pub const fn is_lsb_of_pair(&self) -> bool {
+        self.0 >= 32 && self.0 <= 63
+    }
+
+    /// The other controller of a 14 bit pair, the LSB controller for an MSB controller and the
+    /// other way around. `None` for controllers above 63
+    pub const fn paired_controller(&self) -> Option<Control> {
+        if self.is_msb_of_pair() {
+            Some(Self(self.0 + 32))
+        } else if self.is_lsb_of_pair() {
+            Some(Self(self.0 - 32))
+        } else {
+            None
+        }
+    }
 }
 
 impl From<u8> for Control {
@@ -227,7 +1047,7 @@ impl From<Control> for u8 {
 }
 
 /// A Midi program number, these usually correspond to presets on Midi devices
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Program(u8);
 
@@ -241,9 +1061,49 @@ impl Program {
     /// * The `program` will be clamped so it is in the 0..127 valid range
     ///
     pub const fn new(program: u8) -> Self {
-        debug_assert!(program < 127, "Program exceeds valid range");
+        range_assert!(program <= 127, "Program exceeds valid range");
         Self(if program > 127 { 127 } else { program })
     }
+
+    /// Create a new `Program`, rejecting out of range values instead of clamping them
+    ///
+    /// # Arguments
+    /// * `program` - the value, must be in the 0..127 range
+    ///
+    pub const fn try_new(program: u8) -> Result<Self, MidiError> {
+        if program > 127 {
+            Err(MidiError::ValueOutOfRange)
+        } else {
+            Ok(Self(program))
+        }
+    }
+
+    /// Get the program number value as a `u8`
+    pub const fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// The next program, wrapping from 127 to 0
+    pub const fn next(self) -> Self {
+        self.wrapping_add(1)
+    }
+
+    /// The previous program, wrapping from 0 to 127
+    pub const fn prev(self) -> Self {
+        self.wrapping_add(127)
+    }
+
+    /// Step forward `n` programs, wrapping around from 127 to 0
+    pub const fn wrapping_add(self, n: u8) -> Self {
+        Self(((self.0 as u16 + n as u16) % 128) as u8)
+    }
+}
+
+/// Defaults to program 0
+impl Default for Program {
+    fn default() -> Self {
+        Self(0)
+    }
 }
 
 impl From<u8> for Program {
@@ -259,59 +1119,297 @@ impl From<Program> for u8 {
 }
 
 /// A 7 bit Midi data value stored in an unsigned 8 bit integer, the msb is always 0
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Value7(u8);
 
-impl Value7 {
-    /// Create a new `Value7`
+impl Value7 {
+    /// Create a new `Value7`
+    ///
+    /// # Arguments
+    /// * `value` - the value
+    ///
+    /// # Note
+    /// * The `value` will be clamped so it is in the 0..127 valid range
+    ///
+    pub const fn new(value: u8) -> Self {
+        range_assert!(value <= 127, "Value7 exceeds valid range");
+        Self(if value > 127 { 127 } else { value })
+    }
+
+    /// Create a new `Value7`, rejecting out of range values instead of clamping them
+    ///
+    /// # Arguments
+    /// * `value` - the value, must be in the 0..127 range
+    ///
+    pub const fn try_new(value: u8) -> Result<Self, MidiError> {
+        if value > 127 {
+            Err(MidiError::ValueOutOfRange)
+        } else {
+            Ok(Self(value))
+        }
+    }
+
+    /// The minimum value, 0
+    pub const MIN: Self = Self(0);
+    /// The center value, 64
+    pub const CENTER: Self = Self(64);
+    /// The maximum value, 127
+    pub const MAX: Self = Self(127);
+
+    /// Add to the value, saturating at 127
+    pub const fn saturating_add(self, rhs: u8) -> Self {
+        Self::new(if rhs > 127 - self.0 {
+            127
+        } else {
+            self.0 + rhs
+        })
+    }
+
+    /// Subtract from the value, saturating at 0
+    pub const fn saturating_sub(self, rhs: u8) -> Self {
+        Self(self.0.saturating_sub(rhs))
+    }
+
+    /// Map the value through a gamma curve, `out = 127 * (in / 127) ^ gamma`
+    ///
+    /// # Arguments
+    /// * `gamma` - the curve exponent, values below 1.0 make soft input louder and values above
+    ///   1.0 make it quieter. 1.0 leaves the value unchanged
+    ///
+    /// # Note
+    /// * The result is rounded to the nearest value and clamped to the 0..127 range
+    ///
+    #[cfg(feature = "libm")]
+    pub fn map_curve(self, gamma: f32) -> Self {
+        let out = 127.0 * libm::powf(self.0 as f32 / 127.0, gamma);
+        Self(libm::roundf(out.clamp(0.0, 127.0)) as u8)
+    }
+
+    /// Add a signed amount to the value, saturating at 0 and 127
+    pub const fn saturating_add_signed(self, rhs: i8) -> Self {
+        if rhs < 0 {
+            self.saturating_sub(rhs.unsigned_abs())
+        } else {
+            self.saturating_add(rhs as u8)
+        }
+    }
+
+    /// Scale the value to the 14 bit range, keeping the minimum, center and maximum
+    ///
+    /// # Note
+    /// * The value becomes the MSB. Values up to the center 64 get an LSB of 0, so 0 maps to 0
+    ///   and 64 maps to the center 8192. Above the center the lower 6 bits are repeated into the
+    ///   LSB so 127 maps to the maximum 16383
+    /// * `Value14::to_value7_lossy` is the exact inverse
+    ///
+    pub const fn to_value14(self) -> Value14 {
+        let lsb = if self.0 > 64 {
+            let bits = self.0 & 0x3F;
+            (bits << 1) | (bits >> 5)
+        } else {
+            0
+        };
+        Value14(self.0, lsb)
+    }
+
+    /// Get the value as a `u8`
+    pub const fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Defaults to the minimum value 0
+impl Default for Value7 {
+    fn default() -> Self {
+        Self::MIN
+    }
+}
+
+impl From<u8> for Value7 {
+    fn from(value: u8) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Value7> for u8 {
+    fn from(value: Value7) -> u8 {
+        value.0
+    }
+}
+
+/// Convert from 0.0..1.0
+///
+/// The value is clamped to 0.0..1.0, scaled by 127 and rounded to the nearest value
+impl From<f32> for Value7 {
+    fn from(value: f32) -> Self {
+        Self((value.clamp(0.0, 1.0) * 127.0 + 0.5) as u8)
+    }
+}
+
+/// Convert into 0.0..1.0
+///
+/// The value is divided by 127, so 0 maps to 0.0 and 127 maps to 1.0
+impl From<Value7> for f32 {
+    fn from(value: Value7) -> f32 {
+        value.0 as f32 / 127.0
+    }
+}
+
+/// A 14 bit Midi value stored as two 7 bit Midi data values, where the msb is always 0 to signify
+/// that this is a data value.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Value14(u8, u8);
+
+impl Value14 {
+    /// Create a new `Value14`
+    ///
+    /// # Arguments
+    /// * `msb` - the most significant 7 bits
+    /// * `lsb` - the least significant 7 bits
+    ///
+    /// # Note
+    /// * Each half is clamped on its own so it is in the 0..127 valid range, so `new(200, 0)`
+    ///   becomes `(127, 0)` which is 16256 rather than the maximum 16383. Use
+    ///   `from_u16_clamped` to clamp a combined 14 bit value instead
+    ///
+    pub const fn new(msb: u8, lsb: u8) -> Self {
+        range_assert!(msb <= 127, "Value14 msb exceeds valid range");
+        range_assert!(lsb <= 127, "Value14 lsb exceeds valid range");
+        Value14(
+            if msb > 127 { 127 } else { msb },
+            if lsb > 127 { 127 } else { lsb },
+        )
+    }
+
+    /// The minimum value, 0
+    pub const MIN: Self = Self(0, 0);
+    /// The center value, 8192. This is the neutral position for pitch bend
+    pub const CENTER: Self = Self(64, 0);
+    /// The maximum value, 16383
+    pub const MAX: Self = Self(127, 127);
+
+    /// Get the most significant 7 bits
+    pub const fn msb(&self) -> u8 {
+        self.0
+    }
+
+    /// Get the least significant 7 bits
+    pub const fn lsb(&self) -> u8 {
+        self.1
+    }
+
+    /// Scale the value down to 7 bits by dropping the LSB
+    ///
+    /// # Note
+    /// * This is the inverse of `Value7::to_value14`, the result is the MSB rounded down
+    ///
+    pub const fn to_value7_lossy(self) -> Value7 {
+        Value7(self.0)
+    }
+
+    /// Get the value as a `u16` in the 0..16383 range
+    pub const fn as_u16(&self) -> u16 {
+        ((self.0 as u16) << 7) + self.1 as u16
+    }
+
+    /// Get the value as an `i16` in the -8192..8191 range, the center value maps to 0
+    pub const fn as_i16(&self) -> i16 {
+        self.as_u16() as i16 - 8192
+    }
+
+    /// Create a new `Value14` from its most and least significant 7 bit halves
+    pub const fn from_parts(msb: Value7, lsb: Value7) -> Self {
+        Self(msb.0, lsb.0)
+    }
+
+    /// Split the value into its most and least significant 7 bit halves
+    pub const fn to_parts(self) -> (Value7, Value7) {
+        (Value7(self.0), Value7(self.1))
+    }
+
+    /// Create a new `Value14` from a 14 bit value, usable in const contexts
+    ///
+    /// # Note
+    /// * The `value` will be clamped so it is in the 0..16383 valid range
+    ///
+    pub const fn from_u16_const(value: u16) -> Self {
+        range_assert!(value <= 16383, "Value14 exceeds valid range");
+        Self::from_u16_clamped(value)
+    }
+
+    /// Create a new `Value14` from a 14 bit value, clamping it to the 0..16383 range
+    ///
+    /// # Note
+    /// * Unlike `from_u16_const` values above 16383 are expected and do not trigger the range
+    ///   assertion
+    ///
+    pub const fn from_u16_clamped(value: u16) -> Self {
+        let value = if value > 16383 { 16383 } else { value };
+        Self(((value & 0x3f80) >> 7) as u8, (value & 0x007f) as u8)
+    }
+
+    /// Create a new `Value14` from a value centered on 0, usable in const contexts
+    ///
+    /// # Note
+    /// * The `value` will be clamped so it is in the -8192..8191 valid range
+    ///
+    pub const fn from_i16_const(value: i16) -> Self {
+        range_assert!(value >= -8192, "Value14 exceeds valid range");
+        range_assert!(value <= 8191, "Value14 exceeds valid range");
+        let value = if value < -8192 {
+            -8192
+        } else if value > 8191 {
+            8191
+        } else {
+            value
+        };
+        Self::from_u16_const((value + 8192) as u16)
+    }
+
+    /// Create a pitch bend value from a semitone offset
+    ///
+    /// # Arguments
+    /// * `semitones` - the offset from the center in semitones
+    /// * `range` - the pitch bend range of the receiver in semitones up or down, commonly 2.0
+    ///
+    /// # Note
+    /// * Offsets beyond `range` are clamped to the minimum or maximum value
+    ///
+    pub fn from_semitones(semitones: f32, range: f32) -> Self {
+        Self::from((semitones / range).clamp(-1.0, 1.0))
+    }
+
+    /// Convert a pitch bend value into a semitone offset
     ///
     /// # Arguments
-    /// * `value` - the value
-    ///
-    /// # Note
-    /// * The `value` will be clamped so it is in the 0..127 valid range
+    /// * `range` - the pitch bend range of the receiver in semitones up or down, commonly 2.0
     ///
-    pub const fn new(value: u8) -> Self {
-        debug_assert!(value <= 127, "Value7 exceeds valid range");
-        Self(if value > 127 { 127 } else { value })
+    pub fn to_semitones(self, range: f32) -> f32 {
+        f32::from(self) * range
     }
 }
 
-impl From<u8> for Value7 {
-    fn from(value: u8) -> Self {
-        Self::new(value)
+/// Defaults to the center value 8192, so a default pitch bend is the neutral position rather
+/// than a full bend down
+impl Default for Value14 {
+    fn default() -> Self {
+        Self::CENTER
     }
 }
 
-impl From<Value7> for u8 {
-    fn from(value: Value7) -> u8 {
-        value.0
+/// Orders by the combined 14 bit value
+impl PartialOrd for Value14 {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-/// A 14 bit Midi value stored as two 7 bit Midi data values, where the msb is always 0 to signify
-/// that this is a data value.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Value14(u8, u8);
-
-impl Value14 {
-    /// Create a new `Value14`
-    ///
-    /// # Arguments
-    /// * `val` - the value
-    ///
-    /// # Note
-    /// * The `val` will be clamped so it is in the 0..127 valid range
-    ///
-    pub const fn new(msb: u8, lsb: u8) -> Self {
-        debug_assert!(msb <= 127, "Value14 msb exceeds valid range");
-        debug_assert!(lsb <= 127, "Value14 lsb exceeds valid range");
-        Value14(
-            if msb >= 127 { 127 } else { msb },
-            if lsb >= 127 { 127 } else { lsb },
-        )
+/// Orders by the combined 14 bit value
+impl Ord for Value14 {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        u16::from(*self).cmp(&u16::from(*other))
     }
 }
 
@@ -329,44 +1427,54 @@ impl From<Value14> for (u8, u8) {
 
 impl From<u16> for Value14 {
     fn from(value: u16) -> Self {
-        debug_assert!(value <= 16383, "Value14 exceeds valid range");
-        let value = if value > 16383 { 16383 } else { value };
-        Self(((value & 0x3f80) >> 7) as u8, (value & 0x007f) as u8)
+        Self::from_u16_const(value)
     }
 }
 
 impl From<Value14> for u16 {
     fn from(value: Value14) -> u16 {
-        ((value.0 as u16) << 7) + value.1 as u16
+        value.as_u16()
     }
 }
 
-///Convert from -8192i16..8191i16
+/// Convert from -8192i16..8191i16
+///
+/// The value is offset by 8192, so -8192 maps to 0, 0 maps to the center value 8192 and 8191 maps
+/// to 16383. This is the exact inverse of the conversion into `i16`
 impl From<i16> for Value14 {
     fn from(value: i16) -> Self {
-        debug_assert!(value >= -8192, "Value14 exceeds valid range");
-        debug_assert!(value <= 8191, "Value14 exceeds valid range");
-        let value = value.clamp(-8192, 8191) + 8192;
-        Value14::new(((value & 0x3f80) >> 7) as u8, (value & 0x007f) as u8)
+        Self::from_i16_const(value)
     }
 }
 
-///Convert into -8192i16..8191i16
+/// Convert into -8192i16..8191i16
+///
+/// The center value 8192 maps to 0. This is the exact inverse of the conversion from `i16`
 impl From<Value14> for i16 {
     fn from(value: Value14) -> i16 {
-        let v: u16 = value.into();
-        (v as i16) - 8192
+        value.as_i16()
     }
 }
 
-///Convert from -1.0..1.0
+/// Convert from -1.0..1.0
+///
+/// Positive values are scaled by 8191 and negative values by 8192 before converting from `i16`,
+/// so 0.0 maps to the center value and both -1.0 and 1.0 reach the ends of the range
+///
+/// # Note
+/// * Values outside of -1.0..1.0 are clamped to the ends of the range, like the conversion from
+///   `i16` this panics on them in debug builds or with the `strict` feature
+///
 impl From<f32> for Value14 {
     fn from(value: f32) -> Self {
         Self::from((value * if value > 0.0 { 8191.0 } else { 8192.0 }) as i16)
     }
 }
 
-///Convert into -1.0..1.0
+/// Convert into -1.0..1.0
+///
+/// The inverse of the conversion from `f32`, values above the center are divided by 8191 and
+/// values below it by 8192
 impl From<Value14> for f32 {
     fn from(value: Value14) -> f32 {
         let v: i16 = value.into();
@@ -375,9 +1483,35 @@ impl From<Value14> for f32 {
     }
 }
 
-/*
+/// Convert from -1.0..1.0
+///
+/// Positive values are scaled by 8191 and negative values by 8192 before converting from `i16`,
+/// so 0.0 maps to the center value and both -1.0 and 1.0 reach the ends of the range
+///
+/// # Note
+/// * Values outside of -1.0..1.0 are clamped to the ends of the range, like the conversion from
+///   `i16` this panics on them in debug builds or with the `strict` feature
+///
+impl From<f64> for Value14 {
+    fn from(value: f64) -> Self {
+        Self::from((value * if value > 0.0 { 8191.0 } else { 8192.0 }) as i16)
+    }
+}
+
+/// Convert into -1.0..1.0
+///
+/// The inverse of the conversion from `f64`, values above the center are divided by 8191 and
+/// values below it by 8192
+impl From<Value14> for f64 {
+    fn from(value: Value14) -> f64 {
+        let v: i16 = value.into();
+        let v = v as f64 / if v > 0 { 8191.0 } else { 8192.0 };
+        v.clamp(-1.0, 1.0)
+    }
+}
+
 /// The SMPTE type used. This indicates the number of frames per second
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SmpteType {
     /// 24 frames per second
@@ -393,6 +1527,7 @@ pub enum SmpteType {
     Frames30,
 }
 
+/*
 /// The value of the quarter frame message, this message contains a message type and a value. Each
 /// of these eight messages encodes a 4 bit part of the midi time code. As one of these is sent
 /// every quarter frames, the complete midi time code is sent every two frames.
@@ -426,7 +1561,7 @@ pub enum QuarterFrameType {
 */
 
 /// A MIDI Quarter Frame value, used for sync.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct QuarterFrame(u8);
 
@@ -440,9 +1575,45 @@ impl QuarterFrame {
     /// * The `frame` will be clamped so it is in the 0..127 valid range
     ///
     pub const fn new(frame: u8) -> Self {
-        debug_assert!(frame <= 127, "QuarterFrame exceeds valid range");
+        range_assert!(frame <= 127, "QuarterFrame exceeds valid range");
         Self(if frame > 127 { 127 } else { frame })
     }
+
+    /// Create a new `QuarterFrame`, rejecting out of range values instead of clamping them
+    ///
+    /// # Arguments
+    /// * `frame` - the value, must be in the 0..127 range
+    ///
+    pub const fn try_new(frame: u8) -> Result<Self, MidiError> {
+        if frame > 127 {
+            Err(MidiError::ValueOutOfRange)
+        } else {
+            Ok(Self(frame))
+        }
+    }
+
+    /// Create a new `QuarterFrame` from a message type and a 4 bit value
+    ///
+    /// # Arguments
+    /// * `msg_type` - the message type, which part of the time code this frame carries
+    /// * `value` - the 4 bit value for that part of the time code
+    ///
+    /// # Note
+    /// * The `msg_type` will be clamped so it is in the 0..7 valid range
+    /// * The `value` will be clamped so it is in the 0..15 valid range
+    ///
+    pub const fn from_type_value(msg_type: u8, value: u8) -> Self {
+        range_assert!(msg_type <= 7, "QuarterFrame type exceeds valid range");
+        range_assert!(value <= 15, "QuarterFrame value exceeds valid range");
+        let msg_type = if msg_type > 7 { 7 } else { msg_type };
+        let value = if value > 15 { 15 } else { value };
+        Self((msg_type << 4) | value)
+    }
+
+    /// Get the quarter frame value as a `u8`
+    pub const fn as_u8(&self) -> u8 {
+        self.0
+    }
 }
 
 /*
@@ -475,7 +1646,9 @@ impl From<QuarterFrame> for u8 {
 
 #[cfg(test)]
 mod test {
+    extern crate std;
     use super::*;
+    use std::string::ToString;
 
     #[test]
     fn should_combine_7_bit_vals_into_14() {
@@ -484,6 +1657,99 @@ mod test {
         assert_eq!((0b01010101u8, 0b01010111u8), val.into())
     }
 
+    #[test]
+    fn value7_saturating_arithmetic() {
+        assert_eq!(Value7::new(110), Value7::new(100).saturating_add(10));
+        assert_eq!(Value7::MAX, Value7::new(100).saturating_add(100));
+        assert_eq!(Value7::MAX, Value7::new(100).saturating_add(255));
+        assert_eq!(Value7::new(90), Value7::new(100).saturating_sub(10));
+        assert_eq!(Value7::MIN, Value7::new(10).saturating_sub(100));
+        assert_eq!(Value7::new(95), Value7::new(100).saturating_add_signed(-5));
+        assert_eq!(Value7::MIN, Value7::new(10).saturating_add_signed(-128));
+        assert_eq!(Value7::MAX, Value7::new(100).saturating_add_signed(127));
+    }
+
+    #[cfg(feature = "libm")]
+    #[test]
+    fn value7_curve() {
+        assert_eq!(Value7::new(64), Value7::new(64).map_curve(1.0));
+        assert_eq!(Value7::new(32), Value7::new(64).map_curve(2.0));
+        assert_eq!(Value7::new(90), Value7::new(64).map_curve(0.5));
+        assert_eq!(Value7::MIN, Value7::MIN.map_curve(0.5));
+        assert_eq!(Value7::MAX, Value7::MAX.map_curve(3.0));
+    }
+
+    #[test]
+    fn conversion_f32_7() {
+        assert_eq!(0.0f32, Value7::MIN.into());
+        assert_eq!(1.0f32, Value7::MAX.into());
+        assert_eq!(64.0 / 127.0, f32::from(Value7::CENTER));
+
+        assert_eq!(Value7::MIN, Value7::from(0.0f32));
+        assert_eq!(Value7::MAX, Value7::from(1.0f32));
+        assert_eq!(Value7::new(64), Value7::from(0.5f32));
+        assert_eq!(Value7::MIN, Value7::from(-1.0f32));
+        assert_eq!(Value7::MAX, Value7::from(2.0f32));
+
+        for value in 0..=127u8 {
+            let val = Value7::new(value);
+            assert_eq!(val, Value7::from(f32::from(val)));
+        }
+    }
+
+    #[test]
+    fn value14_msb_lsb() {
+        let val = Value14::from(0b0010101011010111u16);
+        assert_eq!(0b01010101u8, val.msb());
+        assert_eq!(0b01010111u8, val.lsb());
+    }
+
+    #[test]
+    fn value_constants() {
+        assert_eq!(0u8, Value7::MIN.into());
+        assert_eq!(64u8, Value7::CENTER.into());
+        assert_eq!(127u8, Value7::MAX.into());
+        assert_eq!(0u16, Value14::MIN.into());
+        assert_eq!(8192u16, Value14::CENTER.into());
+        assert_eq!(0i16, Value14::CENTER.into());
+        assert_eq!(16383u16, Value14::MAX.into());
+    }
+
+    #[test]
+    fn value14_parts() {
+        let val = Value14::from_parts(Value7::new(0x55), Value7::new(0x57));
+        assert_eq!(0b0010101011010111u16, val.into());
+        assert_eq!((Value7::new(0x55), Value7::new(0x57)), val.to_parts());
+    }
+
+    #[test]
+    fn pitch_bend_semitones() {
+        assert_eq!(Value14::CENTER, Value14::from_semitones(0.0, 2.0));
+        assert_eq!(Value14::MAX, Value14::from_semitones(2.0, 2.0));
+        assert_eq!(Value14::MIN, Value14::from_semitones(-2.0, 2.0));
+        assert_eq!(Value14::MAX, Value14::from_semitones(12.0, 2.0));
+        assert_eq!(Value14::from(-4096i16), Value14::from_semitones(-6.0, 12.0));
+
+        assert_eq!(0.0, Value14::CENTER.to_semitones(2.0));
+        assert_eq!(2.0, Value14::MAX.to_semitones(2.0));
+        assert_eq!(-12.0, Value14::MIN.to_semitones(12.0));
+        assert_eq!(-6.0, Value14::from(-4096i16).to_semitones(12.0));
+    }
+
+    #[test]
+    fn order_value14_numerically() {
+        assert!(Value14::from(0u16) < Value14::from(100u16));
+        assert!(Value14::new(1, 0) > Value14::new(0, 127));
+        assert!(Value14::MIN < Value14::CENTER);
+        assert!(Value14::CENTER < Value14::MAX);
+
+        for a in (0..=16383u16).step_by(127) {
+            for b in (0..=16383u16).step_by(131) {
+                assert_eq!(a.cmp(&b), Value14::from(a).cmp(&Value14::from(b)));
+            }
+        }
+    }
+
     #[test]
     fn conversion_u16_14() {
         let val: Value14 = Value14::from(16383u16);
@@ -527,6 +1793,26 @@ mod test {
         assert_eq!(1i16, val.into());
     }
 
+    #[test]
+    fn roundtrip_i16_14() {
+        for value in -8192i16..=8191 {
+            let val = Value14::from(value);
+            assert_eq!(value, i16::from(val));
+            assert_eq!(value as i32 + 8192, u16::from(val) as i32);
+        }
+    }
+
+    #[test]
+    fn roundtrip_f32_near_center() {
+        for value in -64i16..=64 {
+            let val = Value14::from(value);
+            assert_eq!(val, Value14::from(f32::from(val)), "{}", value);
+        }
+        assert_eq!(Value14::CENTER, Value14::from(0.0f32));
+        assert_eq!(Value14::from(1i16), Value14::from(1.0f32 / 8191.0));
+        assert_eq!(Value14::from(-1i16), Value14::from(-1.0f32 / 8192.0));
+    }
+
     #[test]
     fn conversion_f32_14() {
         let val: Value14 = Value14::from(0.0f32);
@@ -541,4 +1827,816 @@ mod test {
         assert_eq!((0, 0), val.into());
         assert_eq!(-1.0f32, val.into());
     }
+
+    #[test]
+    fn conversion_f64_14() {
+        let val: Value14 = Value14::from(0.0f64);
+        assert_eq!((64, 0), val.into());
+        assert_eq!(0.0f64, val.into());
+
+        let val: Value14 = Value14::from(1.0f64);
+        assert_eq!((127, 127), val.into());
+        assert_eq!(1.0f64, val.into());
+
+        let val: Value14 = Value14::from(-1.0f64);
+        assert_eq!((0, 0), val.into());
+        assert_eq!(-1.0f64, val.into());
+
+        let val: Value14 = Value14::from(0.5f64);
+        assert_eq!(4095i16, val.into());
+        assert_eq!(4095.0 / 8191.0, f64::from(val));
+    }
+
+    #[test]
+    fn const_u8_accessors() {
+        const CHANNEL: u8 = Channel::C16.as_u8();
+        assert_eq!(15, CHANNEL);
+        assert_eq!(7, Control::new(7).as_u8());
+        assert_eq!(42, Program::new(42).as_u8());
+        assert_eq!(100, Value7::new(100).as_u8());
+        assert_eq!(0x35, QuarterFrame::new(0x35).as_u8());
+    }
+
+    #[test]
+    fn channel_index_and_number() {
+        assert_eq!(0, Channel::C1.index());
+        assert_eq!(1, Channel::C1.number());
+        assert_eq!(15, Channel::C16.index());
+        assert_eq!(16, Channel::C16.number());
+    }
+
+    #[test]
+    fn channel_from_number() {
+        assert_eq!(Channel::C1, Channel::from_number(1));
+        assert_eq!(Channel::C16, Channel::from_number(16));
+        for channel in Channel::all() {
+            assert_eq!(channel, Channel::from_number(channel.number()));
+        }
+    }
+
+    #[test]
+    fn channel_display() {
+        assert_eq!("1", Channel::C1.to_string());
+        assert_eq!("16", Channel::C16.to_string());
+    }
+
+    #[test]
+    fn quarter_frame_from_type_and_value() {
+        for msg_type in 0..8 {
+            let frame = QuarterFrame::from_type_value(msg_type, 15 - msg_type);
+            assert_eq!(msg_type, frame.as_u8() >> 4);
+            assert_eq!(15 - msg_type, frame.as_u8() & 0x0F);
+        }
+    }
+
+    #[test]
+    fn iterate_all_channels() {
+        let mut channels = Channel::all();
+        assert_eq!(16, channels.len());
+        assert_eq!(Some(Channel::C1), channels.next());
+        assert_eq!(Some(Channel::C16), channels.next_back());
+        assert_eq!(Some(Channel::C2), channels.next());
+        assert_eq!(13, channels.len());
+    }
+
+    #[test]
+    fn control_and_program_accept_127() {
+        assert_eq!(127, Control::new(127).as_u8());
+        assert_eq!(127, Program::new(127).as_u8());
+    }
+
+    #[test]
+    fn control_pairs() {
+        let volume = Control::new(7);
+        assert!(volume.is_msb_of_pair());
+        assert!(!volume.is_lsb_of_pair());
+        assert_eq!(Some(Control::new(39)), volume.paired_controller());
+
+        let volume_lsb = Control::new(39);
+        assert!(!volume_lsb.is_msb_of_pair());
+        assert!(volume_lsb.is_lsb_of_pair());
+        assert_eq!(Some(volume), volume_lsb.paired_controller());
+
+        assert_eq!(Some(Control::new(32)), Control::new(0).paired_controller());
+        assert_eq!(Some(Control::new(31)), Control::new(63).paired_controller());
+
+        let sustain = Control::new(64);
+        assert!(!sustain.is_msb_of_pair());
+        assert!(!sustain.is_lsb_of_pair());
+        assert_eq!(None, sustain.paired_controller());
+    }
+
+    #[test]
+    fn channel_mask() {
+        let mut mask = ChannelMask::none();
+        assert!(mask.is_empty());
+        mask.insert(Channel::C1);
+        mask.insert(Channel::C2);
+        mask.insert(Channel::C10);
+        assert!(mask.contains(Channel::C10));
+        assert!(!mask.contains(Channel::C3));
+        assert_eq!(0b0000_0010_0000_0011, mask.as_u16());
+
+        mask.remove(Channel::C2);
+        let mut channels = mask.iter();
+        assert_eq!(Some(Channel::C1), channels.next());
+        assert_eq!(Some(Channel::C10), channels.next());
+        assert_eq!(None, channels.next());
+
+        const DRUMS: ChannelMask = ChannelMask::none().with(Channel::C10);
+        assert_eq!(DRUMS, [Channel::C10].into_iter().collect());
+        assert_eq!(16, ChannelMask::all().iter().count());
+        assert!(ChannelMask::all().contains(Channel::C16));
+    }
+
+    #[test]
+    fn status_byte_matches_constants() {
+        let ch = Channel::C3;
+        let cases = [
+            (MidiMessage::note_off(2, 60, 0), status::NOTE_OFF | 2),
+            (MidiMessage::note_on(2, 60, 1), status::NOTE_ON | 2),
+            (
+                MidiMessage::KeyPressure(ch, Note::new(60), Value7::new(1)),
+                status::KEY_PRESSURE | 2,
+            ),
+            (
+                MidiMessage::control_change(2, 1, 1),
+                status::CONTROL_CHANGE | 2,
+            ),
+            (
+                MidiMessage::program_change(2, 1),
+                status::PROGRAM_CHANGE | 2,
+            ),
+            (
+                MidiMessage::ChannelPressure(ch, Value7::new(1)),
+                status::CHANNEL_PRESSURE | 2,
+            ),
+            (
+                MidiMessage::pitch_bend(2, Value14::MAX),
+                status::PITCH_BEND_CHANGE | 2,
+            ),
+            (
+                MidiMessage::QuarterFrame(QuarterFrame::new(1)),
+                status::QUARTER_FRAME,
+            ),
+            (
+                MidiMessage::SongPositionPointer(Value14::MAX),
+                status::SONG_POSITION_POINTER,
+            ),
+            (MidiMessage::SongSelect(Value7::new(1)), status::SONG_SELECT),
+            (MidiMessage::TuneRequest, status::TUNE_REQUEST),
+            (MidiMessage::TimingClock, status::TIMING_CLOCK),
+            (MidiMessage::Start, status::START),
+            (MidiMessage::Continue, status::CONTINUE),
+            (MidiMessage::Stop, status::STOP),
+            (MidiMessage::ActiveSensing, status::ACTIVE_SENSING),
+            (MidiMessage::Reset, status::RESET),
+        ];
+        for (message, status) in cases {
+            assert_eq!(status, message.status_byte());
+            assert_eq!(
+                Some(message.len()),
+                MidiMessage::message_len_from_status(status)
+            );
+        }
+    }
+
+    #[test]
+    fn iterate_message_bytes() {
+        let mut bytes = MidiMessage::pitch_bend(1, Value14::new(0x40, 0x01)).bytes();
+        assert_eq!(3, bytes.len());
+        assert_eq!(Some(0xE1), bytes.next());
+        assert_eq!(Some(0x01), bytes.next());
+        assert_eq!(Some(0x40), bytes.next());
+        assert_eq!(None, bytes.next());
+
+        let mut bytes = MidiMessage::program_change(2, 5).bytes();
+        assert_eq!(2, bytes.len());
+        assert_eq!(Some(0xC2), bytes.next());
+        assert_eq!(Some(5), bytes.next());
+        assert_eq!(None, bytes.next());
+
+        let mut bytes = MidiMessage::TimingClock.bytes();
+        assert_eq!(Some(0xF8), bytes.next());
+        assert_eq!(None, bytes.next());
+    }
+
+    #[test]
+    fn owned_message_bytes() {
+        let bytes = MidiMessage::note_on(1, 60, 100).to_midi_bytes();
+        assert_eq!(3, bytes.len());
+        assert_eq!(&[0x91, 60, 100], bytes.as_ref());
+
+        let bytes = MidiMessage::ChannelPressure(Channel::C3, Value7::new(5)).to_midi_bytes();
+        assert_eq!(&[0xD2, 5], bytes.as_slice());
+        let mut iter = bytes.into_iter();
+        assert_eq!(Some(0xD2), iter.next());
+        assert_eq!(Some(5), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn scale_between_resolutions() {
+        assert_eq!(Value14::MIN, Value7::new(0).to_value14());
+        assert_eq!(Value14::CENTER, Value7::new(64).to_value14());
+        assert_eq!(Value14::MAX, Value7::new(127).to_value14());
+        assert_eq!(Value14::new(65, 2), Value7::new(65).to_value14());
+
+        assert_eq!(Value7::new(0), Value14::MIN.to_value7_lossy());
+        assert_eq!(Value7::new(64), Value14::CENTER.to_value7_lossy());
+        assert_eq!(Value7::new(127), Value14::MAX.to_value7_lossy());
+        assert_eq!(Value7::new(64), Value14::new(64, 127).to_value7_lossy());
+
+        for value in 0..=127 {
+            let value = Value7::new(value);
+            assert_eq!(value, value.to_value14().to_value7_lossy());
+        }
+    }
+
+    #[test]
+    fn value14_clamp_combined_value() {
+        assert_eq!(Value14::MAX, Value14::from_u16_clamped(20000));
+        assert_eq!(Value14::MAX, Value14::from_u16_clamped(u16::MAX));
+        assert_eq!(Value14::new(64, 1), Value14::from_u16_clamped(8193));
+    }
+
+    #[test]
+    fn value14_as_integers() {
+        const BEND: i16 = Value14::MIN.as_i16();
+        assert_eq!(-8192, BEND);
+        assert_eq!(0, Value14::MIN.as_u16());
+        assert_eq!(8192, Value14::CENTER.as_u16());
+        assert_eq!(0, Value14::CENTER.as_i16());
+        assert_eq!(16383, Value14::MAX.as_u16());
+        assert_eq!(8191, Value14::MAX.as_i16());
+        assert_eq!(u16::from(Value14::new(3, 4)), Value14::new(3, 4).as_u16());
+    }
+
+    #[test]
+    fn build_const_message_tables() {
+        const MESSAGES: [MidiMessage; 3] = [
+            MidiMessage::NoteOn(Channel::C1, Note::new(60), Value7::new(100)),
+            MidiMessage::PitchBendChange(Channel::C2, Value14::from_i16_const(-8192)),
+            MidiMessage::ControlChange(Channel::new(2), Control::new(1), Value7::MAX),
+        ];
+        assert_eq!(MidiMessage::note_on(0, 60, 100), MESSAGES[0]);
+        assert_eq!(
+            MidiMessage::PitchBendChange(Channel::C2, Value14::MIN),
+            MESSAGES[1]
+        );
+        assert_eq!(Value14::from(1000u16), Value14::from_u16_const(1000));
+        assert_eq!(Value14::MAX, Value14::from_u16_const(16383));
+        assert_eq!(Value14::from(-1i16), Value14::from_i16_const(-1));
+    }
+
+    #[test]
+    fn step_programs_with_wraparound() {
+        assert_eq!(Program::new(1), Program::new(0).next());
+        assert_eq!(Program::new(0), Program::new(127).next());
+        assert_eq!(Program::new(126), Program::new(127).prev());
+        assert_eq!(Program::new(127), Program::new(0).prev());
+        assert_eq!(Program::new(2), Program::new(120).wrapping_add(10));
+        assert_eq!(Program::new(127), Program::new(0).wrapping_add(255));
+    }
+
+    #[test]
+    fn try_new_rejects_out_of_range() {
+        assert_eq!(Ok(Channel::C16), Channel::try_new(15));
+        assert_eq!(Err(MidiError::ValueOutOfRange), Channel::try_new(16));
+        assert_eq!(Ok(Control::new(120)), Control::try_new(120));
+        assert_eq!(Err(MidiError::ValueOutOfRange), Control::try_new(128));
+        assert_eq!(Ok(Program::new(0)), Program::try_new(0));
+        assert_eq!(Err(MidiError::ValueOutOfRange), Program::try_new(200));
+        assert_eq!(Ok(Value7::new(127)), Value7::try_new(127));
+        assert_eq!(Err(MidiError::ValueOutOfRange), Value7::try_new(128));
+        assert_eq!(Ok(QuarterFrame::new(0x7F)), QuarterFrame::try_new(0x7F));
+        assert_eq!(Err(MidiError::ValueOutOfRange), QuarterFrame::try_new(0x80));
+    }
+
+    #[test]
+    fn default_values() {
+        assert_eq!(Channel::C1, Channel::default());
+        assert_eq!(Program::new(0), Program::default());
+        assert_eq!(Value7::new(0), Value7::default());
+        assert_eq!(Value14::CENTER, Value14::default());
+        assert_eq!(0i16, Value14::default().into());
+    }
+
+    #[test]
+    fn order_newtypes() {
+        assert!(Channel::C1 < Channel::C16);
+        assert!(Value7::new(10) < Value7::new(11));
+        assert_eq!(Value7::new(80), Value7::new(80).max(Value7::new(20)));
+        assert!(QuarterFrame::new(0x10) > QuarterFrame::new(0x0F));
+    }
+
+    #[test]
+    fn order_program_and_control() {
+        assert!(Program::new(10) < Program::new(20));
+        assert!(Control::new(127) > Control::new(0));
+        assert_eq!(Program::new(5).max(Program::new(3)), Program::new(5));
+    }
+
+    #[test]
+    fn message_channel_and_note() {
+        assert_eq!(
+            Some(Channel::C3),
+            MidiMessage::note_on(2, 60, 100).channel()
+        );
+        assert_eq!(
+            Some(Channel::C16),
+            MidiMessage::pitch_bend(15, Value14::CENTER).channel()
+        );
+        assert_eq!(None, MidiMessage::TimingClock.channel());
+
+        assert_eq!(Some(Note::new(60)), MidiMessage::note_on(2, 60, 100).note());
+        assert_eq!(Some(Note::new(61)), MidiMessage::note_off(2, 61, 0).note());
+        assert_eq!(
+            Some(Note::new(62)),
+            MidiMessage::KeyPressure(Channel::C1, Note::new(62), Value7::new(1)).note()
+        );
+        assert_eq!(None, MidiMessage::control_change(0, 60, 0).note());
+        assert_eq!(None, MidiMessage::Start.note());
+    }
+
+    #[test]
+    fn message_channel_index() {
+        assert_eq!(Some(0), MidiMessage::note_on(0, 60, 100).channel_index());
+        assert_eq!(Some(15), MidiMessage::program_change(15, 1).channel_index());
+        assert_eq!(None, MidiMessage::Reset.channel_index());
+
+        let mut messages = [
+            MidiMessage::note_on(3, 60, 100),
+            MidiMessage::TimingClock,
+            MidiMessage::control_change(1, 7, 100),
+        ];
+        messages.sort_by_key(MidiMessage::channel_index);
+        assert_eq!(
+            [
+                MidiMessage::TimingClock,
+                MidiMessage::control_change(1, 7, 100),
+                MidiMessage::note_on(3, 60, 100),
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn message_velocity() {
+        assert_eq!(
+            Some(Value7::new(100)),
+            MidiMessage::note_on(0, 60, 100).velocity()
+        );
+        assert_eq!(
+            Some(Value7::new(0)),
+            MidiMessage::note_off(0, 60, 0).velocity()
+        );
+        assert_eq!(
+            Some(Value7::new(5)),
+            MidiMessage::KeyPressure(Channel::C1, Note::new(60), Value7::new(5)).velocity()
+        );
+        assert_eq!(
+            Some(Value7::new(6)),
+            MidiMessage::ChannelPressure(Channel::C1, Value7::new(6)).velocity()
+        );
+        assert_eq!(None, MidiMessage::control_change(0, 1, 2).velocity());
+        assert_eq!(None, MidiMessage::SongSelect(Value7::new(1)).velocity());
+    }
+
+    #[test]
+    fn message_control() {
+        let message = MidiMessage::control_change(0, 7, 100);
+        assert_eq!(Some(Control::new(7)), message.control());
+        assert_eq!(Some(Value7::new(100)), message.control_value());
+        assert_eq!(None, MidiMessage::note_on(0, 7, 100).control());
+        assert_eq!(None, MidiMessage::note_on(0, 7, 100).control_value());
+    }
+
+    #[test]
+    fn message_float_values() {
+        assert_eq!(
+            Some(-1.0),
+            MidiMessage::pitch_bend(0, Value14::MIN).pitch_bend_f32()
+        );
+        assert_eq!(
+            Some(0.0),
+            MidiMessage::pitch_bend(0, Value14::CENTER).pitch_bend_f32()
+        );
+        assert_eq!(
+            Some(1.0),
+            MidiMessage::ChannelPressure(Channel::C1, Value7::MAX).channel_pressure_f32()
+        );
+        assert_eq!(None, MidiMessage::Start.pitch_bend_f32());
+        assert_eq!(
+            None,
+            MidiMessage::note_on(0, 60, 127).channel_pressure_f32()
+        );
+    }
+
+    #[test]
+    fn message_program() {
+        assert_eq!(
+            Some(Program::new(42)),
+            MidiMessage::program_change(3, 42).program()
+        );
+        assert_eq!(None, MidiMessage::control_change(3, 42, 0).program());
+        assert_eq!(Some(42), MidiMessage::program_change(3, 42).program_value());
+        assert_eq!(None, MidiMessage::Stop.program_value());
+    }
+
+    #[test]
+    fn message_pitch_bend_value() {
+        assert_eq!(
+            Some(Value14::new(10, 20)),
+            MidiMessage::pitch_bend(0, Value14::new(10, 20)).pitch_bend_value()
+        );
+        assert_eq!(
+            None,
+            MidiMessage::control_change(0, 1, 2).pitch_bend_value()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn render_to_vec() {
+        use std::vec;
+
+        assert_eq!(
+            vec![0x92, 60, 100],
+            MidiMessage::note_on(2, 60, 100).to_vec()
+        );
+        assert_eq!(vec![0xC0, 5], MidiMessage::program_change(0, 5).to_vec());
+        assert_eq!(
+            vec![0xE1, 0x01, 0x40],
+            MidiMessage::pitch_bend(1, Value14::new(0x40, 0x01)).to_vec()
+        );
+        assert_eq!(
+            vec![0xF2, 0x02, 0x10],
+            MidiMessage::SongPositionPointer(Value14::new(0x10, 0x02)).to_vec()
+        );
+        assert_eq!(vec![0xF8], MidiMessage::TimingClock.to_vec());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn render_to_heapless() {
+        assert_eq!(
+            &[0x92, 60, 100],
+            &MidiMessage::note_on(2, 60, 100).to_heapless()[..]
+        );
+        assert_eq!(
+            &[0xD3, 90],
+            &MidiMessage::ChannelPressure(Channel::C4, Value7::new(90)).to_heapless()[..]
+        );
+        assert_eq!(&[0xFF], &MidiMessage::Reset.to_heapless()[..]);
+    }
+
+    #[test]
+    fn message_len_from_status_byte() {
+        let expected = [
+            (0x80, Some(3)),
+            (0x93, Some(3)),
+            (0xC0, Some(2)),
+            (0xD0, Some(2)),
+            (0xE0, Some(3)),
+            (0xF1, Some(2)),
+            (0xF2, Some(3)),
+            (0xF3, Some(2)),
+            (0xF6, Some(1)),
+            (0xF8, Some(1)),
+            (0x40, None),
+            (0xF0, None),
+            (0xF4, None),
+            (0xFD, None),
+        ];
+        for (status, len) in expected {
+            assert_eq!(
+                len,
+                MidiMessage::message_len_from_status(status),
+                "status {:#04X}",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn data_bytes_for_status_byte() {
+        assert_eq!(Some(2), MidiMessage::data_bytes_for_status(0x80));
+        assert_eq!(Some(1), MidiMessage::data_bytes_for_status(0xDF));
+        assert_eq!(Some(1), MidiMessage::data_bytes_for_status(0xF3));
+        assert_eq!(Some(0), MidiMessage::data_bytes_for_status(0xF6));
+        assert_eq!(Some(0), MidiMessage::data_bytes_for_status(0xFE));
+        assert_eq!(None, MidiMessage::data_bytes_for_status(0xF0));
+        assert_eq!(None, MidiMessage::data_bytes_for_status(0xF7));
+        assert_eq!(None, MidiMessage::data_bytes_for_status(0x7F));
+    }
+
+    #[test]
+    fn message_try_from_bytes() {
+        assert_eq!(
+            Ok(MidiMessage::note_on(1, 60, 100)),
+            MidiMessage::try_from(&[0x91, 60, 100, 0xF8][..])
+        );
+        assert_eq!(
+            Ok(MidiMessage::TimingClock),
+            MidiMessage::try_from(&[0xF8][..])
+        );
+        assert_eq!(
+            Err(MidiError::UnexpectedEnd),
+            MidiMessage::try_from(&[][..])
+        );
+        assert_eq!(
+            Err(MidiError::UnexpectedEnd),
+            MidiMessage::try_from(&[0x91, 60][..])
+        );
+        assert_eq!(
+            Err(MidiError::InvalidStatus),
+            MidiMessage::try_from(&[0xF0, 0x7E, 0xF7][..])
+        );
+        assert_eq!(
+            Err(MidiError::InvalidStatus),
+            MidiMessage::try_from(&[60, 100][..])
+        );
+        assert_eq!(
+            Err(MidiError::ValueOutOfRange),
+            MidiMessage::try_from(&[0x91, 0x80, 100][..])
+        );
+    }
+
+    #[test]
+    fn message_from_status_and_data() {
+        assert_eq!(
+            Some(MidiMessage::note_on(3, 60, 100)),
+            MidiMessage::from_status_and_data(0x93, 60, 100)
+        );
+        assert_eq!(
+            Some(MidiMessage::program_change(0, 5)),
+            MidiMessage::from_status_and_data(0xC0, 5, 0xFF)
+        );
+        assert_eq!(
+            Some(MidiMessage::pitch_bend(1, Value14::new(0x40, 0x01))),
+            MidiMessage::from_status_and_data(0xE1, 0x01, 0x40)
+        );
+        assert_eq!(
+            Some(MidiMessage::SongPositionPointer(Value14::new(0x10, 0x02))),
+            MidiMessage::from_status_and_data(0xF2, 0x02, 0x10)
+        );
+        assert_eq!(
+            Some(MidiMessage::Reset),
+            MidiMessage::from_status_and_data(0xFF, 0xFF, 0xFF)
+        );
+        assert_eq!(None, MidiMessage::from_status_and_data(0xF0, 0, 0));
+        assert_eq!(None, MidiMessage::from_status_and_data(0xF4, 0, 0));
+        assert_eq!(None, MidiMessage::from_status_and_data(0x40, 0, 0));
+        assert_eq!(None, MidiMessage::from_status_and_data(0x90, 60, 0x80));
+    }
+
+    #[test]
+    fn message_size() {
+        assert_eq!(4, core::mem::size_of::<MidiMessage>());
+        assert_eq!(4, core::mem::size_of::<Option<MidiMessage>>());
+    }
+
+    #[test]
+    fn hash_messages() {
+        let mut set = std::collections::HashSet::new();
+        assert!(set.insert(MidiMessage::note_on(0, 60, 100)));
+        assert!(set.insert(MidiMessage::note_on(1, 60, 100)));
+        assert!(!set.insert(MidiMessage::note_on(0, 60, 100)));
+    }
+
+    #[test]
+    fn const_message_constructors() {
+        const MESSAGES: [MidiMessage; 5] = [
+            MidiMessage::note_on(0, 60, 100),
+            MidiMessage::note_off(1, 61, 0),
+            MidiMessage::control_change(2, 7, 127),
+            MidiMessage::program_change(3, 42),
+            MidiMessage::pitch_bend(4, Value14::CENTER),
+        ];
+        assert_eq!(
+            MESSAGES,
+            [
+                MidiMessage::NoteOn(Channel::C1, Note::new(60), Value7::new(100)),
+                MidiMessage::NoteOff(Channel::C2, Note::new(61), Value7::new(0)),
+                MidiMessage::ControlChange(Channel::C3, Control::new(7), Value7::new(127)),
+                MidiMessage::ProgramChange(Channel::C4, Program::new(42)),
+                MidiMessage::PitchBendChange(Channel::C5, Value14::new(64, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn bank_select_sequence() {
+        assert_eq!(
+            [
+                MidiMessage::control_change(9, 0, 121),
+                MidiMessage::control_change(9, 32, 1),
+                MidiMessage::program_change(9, 24),
+            ],
+            MidiMessage::bank_select(9, Value14::new(121, 1), Program::new(24))
+        );
+    }
+
+    #[test]
+    fn channel_mode_messages() {
+        let expected = [
+            (MidiMessage::all_sound_off(1), 120, 0),
+            (MidiMessage::reset_all_controllers(1), 121, 0),
+            (MidiMessage::local_control(1, true), 122, 127),
+            (MidiMessage::local_control(1, false), 122, 0),
+            (MidiMessage::all_notes_off(1), 123, 0),
+            (MidiMessage::omni_off(1), 124, 0),
+            (MidiMessage::omni_on(1), 125, 0),
+            (MidiMessage::mono_on(1, Value7::new(4)), 126, 4),
+            (MidiMessage::poly_on(1), 127, 0),
+        ];
+        for (message, control, value) in expected {
+            match message {
+                MidiMessage::ControlChange(channel, c, v) => {
+                    assert_eq!(Channel::C2, channel);
+                    assert_eq!(control, c.as_u8());
+                    assert_eq!(value, v.as_u8());
+                }
+                _ => panic!("expected a control change"),
+            }
+            assert!(message.is_channel_mode());
+        }
+
+        assert!(!MidiMessage::control_change(0, 119, 0).is_channel_mode());
+        assert!(!MidiMessage::note_on(0, 120, 0).is_channel_mode());
+    }
+
+    #[test]
+    fn pair_note_on_and_note_off() {
+        let note_on = MidiMessage::note_on(1, 60, 100);
+        assert!(note_on.matches_note(&MidiMessage::note_off(1, 60, 0)));
+        assert!(note_on.matches_note(&MidiMessage::note_on(1, 60, 0)));
+        assert!(!note_on.matches_note(&MidiMessage::note_off(2, 60, 0)));
+        assert!(!note_on.matches_note(&MidiMessage::note_off(1, 61, 0)));
+        assert!(!note_on.matches_note(&MidiMessage::control_change(1, 60, 0)));
+
+        assert!(MidiMessage::note_off(1, 60, 64).is_note_off_for(Channel::C2, Note::new(60)));
+        assert!(MidiMessage::note_on(1, 60, 0).is_note_off_for(Channel::C2, Note::new(60)));
+        assert!(!note_on.is_note_off_for(Channel::C2, Note::new(60)));
+        assert!(!MidiMessage::note_off(1, 60, 64).is_note_off_for(Channel::C1, Note::new(60)));
+    }
+
+    #[test]
+    fn messages_affecting_voice() {
+        let (channel, note) = (Channel::C2, Note::new(60));
+        assert!(MidiMessage::note_on(1, 60, 100).affects(channel, note));
+        assert!(MidiMessage::note_off(1, 60, 0).affects(channel, note));
+        assert!(MidiMessage::KeyPressure(channel, note, Value7::new(1)).affects(channel, note));
+        assert!(!MidiMessage::note_on(1, 61, 100).affects(channel, note));
+        assert!(!MidiMessage::note_on(0, 60, 100).affects(channel, note));
+
+        assert!(MidiMessage::control_change(1, 74, 10).affects(channel, note));
+        assert!(MidiMessage::ChannelPressure(channel, Value7::new(1)).affects(channel, note));
+        assert!(MidiMessage::pitch_bend(1, Value14::MAX).affects(channel, note));
+        assert!(!MidiMessage::pitch_bend(2, Value14::MAX).affects(channel, note));
+
+        assert!(!MidiMessage::program_change(1, 3).affects(channel, note));
+        assert!(!MidiMessage::Reset.affects(channel, note));
+    }
+
+    #[test]
+    fn remap_message_channel() {
+        assert_eq!(
+            MidiMessage::note_on(9, 60, 100),
+            MidiMessage::note_on(0, 60, 100).map_channel(|_| Channel::C10)
+        );
+        assert_eq!(
+            MidiMessage::pitch_bend(3, Value14::MAX),
+            MidiMessage::pitch_bend(2, Value14::MAX)
+                .map_channel(|channel| Channel::new(channel.index() + 1))
+        );
+        assert_eq!(
+            MidiMessage::program_change(15, 1),
+            MidiMessage::program_change(0, 1).with_channel(Channel::C16)
+        );
+        assert_eq!(
+            MidiMessage::TimingClock,
+            MidiMessage::TimingClock.map_channel(|_| Channel::C10)
+        );
+    }
+
+    #[test]
+    fn transpose_note_messages() {
+        assert_eq!(
+            MidiMessage::NoteOn(Channel::C1, Note::C5, Value7::new(100)),
+            MidiMessage::NoteOn(Channel::C1, Note::C4, Value7::new(100)).transpose(12)
+        );
+        assert_eq!(
+            MidiMessage::note_off(0, 57, 64),
+            MidiMessage::note_off(0, 60, 64).transpose(-3)
+        );
+        assert_eq!(
+            MidiMessage::KeyPressure(Channel::C2, Note::MAX, Value7::new(1)),
+            MidiMessage::KeyPressure(Channel::C2, Note::C8, Value7::new(1)).transpose(100)
+        );
+        assert_eq!(
+            MidiMessage::control_change(0, 60, 1),
+            MidiMessage::control_change(0, 60, 1).transpose(12)
+        );
+    }
+
+    #[test]
+    fn sort_by_transmit_priority() {
+        let mut messages = [
+            MidiMessage::KeyPressure(Channel::C1, Note::new(60), Value7::new(10)),
+            MidiMessage::note_on(0, 60, 100),
+            MidiMessage::note_on(0, 60, 0),
+            MidiMessage::note_off(0, 62, 0),
+            MidiMessage::program_change(0, 1),
+            MidiMessage::control_change(0, 0, 1),
+            MidiMessage::control_change(0, 32, 2),
+            MidiMessage::TimingClock,
+        ];
+        messages.sort_by_key(|message| message.transmit_priority());
+        assert_eq!(
+            [
+                MidiMessage::TimingClock,
+                MidiMessage::control_change(0, 0, 1),
+                MidiMessage::control_change(0, 32, 2),
+                MidiMessage::program_change(0, 1),
+                MidiMessage::note_on(0, 60, 0),
+                MidiMessage::note_off(0, 62, 0),
+                MidiMessage::note_on(0, 60, 100),
+                MidiMessage::KeyPressure(Channel::C1, Note::new(60), Value7::new(10)),
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn note_off_like_messages() {
+        assert!(MidiMessage::note_off(0, 60, 100).is_note_off_like());
+        assert!(MidiMessage::note_on(0, 60, 0).is_note_off_like());
+        assert!(!MidiMessage::note_on(0, 60, 1).is_note_off_like());
+        assert!(!MidiMessage::control_change(0, 0, 0).is_note_off_like());
+    }
+
+    #[test]
+    fn normalize_zero_velocity_note_on() {
+        assert_eq!(
+            MidiMessage::note_off(3, 60, 64),
+            MidiMessage::note_on(3, 60, 0).normalize_note_off()
+        );
+        assert_eq!(
+            MidiMessage::note_on(3, 60, 1),
+            MidiMessage::note_on(3, 60, 1).normalize_note_off()
+        );
+        assert_eq!(
+            MidiMessage::note_off(3, 60, 0),
+            MidiMessage::note_off(3, 60, 0).normalize_note_off()
+        );
+    }
+
+    #[test]
+    fn compare_note_off_encodings() {
+        let off = MidiMessage::note_off(3, 60, 64);
+        assert!(off.semantically_eq(&MidiMessage::note_on(3, 60, 0)));
+        assert!(MidiMessage::note_on(3, 60, 0).semantically_eq(&off));
+        assert!(off.semantically_eq(&MidiMessage::note_off(3, 60, 0)));
+        assert!(!off.semantically_eq(&MidiMessage::note_on(3, 61, 0)));
+        assert!(!off.semantically_eq(&MidiMessage::note_on(4, 60, 0)));
+        assert!(!off.semantically_eq(&MidiMessage::note_on(3, 60, 64)));
+        assert!(MidiMessage::note_on(3, 60, 1).semantically_eq(&MidiMessage::note_on(3, 60, 1)));
+        assert!(!MidiMessage::note_on(3, 60, 1).semantically_eq(&MidiMessage::note_on(3, 60, 2)));
+    }
+
+    #[test]
+    fn split_and_combine_14bit_control_change() {
+        let messages =
+            MidiMessage::control_change_14bit(Channel::C2, Control::new(7), Value14::new(100, 27))
+                .unwrap();
+        assert_eq!(
+            messages,
+            [
+                MidiMessage::ControlChange(Channel::C2, Control::new(7), Value7::new(100)),
+                MidiMessage::ControlChange(Channel::C2, Control::new(39), Value7::new(27)),
+            ]
+        );
+
+        assert_eq!(
+            MidiMessage::combine_control_change_14bit(messages[0], messages[1]),
+            Some(Value14::new(100, 27))
+        );
+    }
+
+    #[test]
+    fn reject_unpaired_14bit_control_change() {
+        assert_eq!(
+            MidiMessage::control_change_14bit(Channel::C1, Control::new(32), Value14::new(0, 0)),
+            None
+        );
+
+        let msb = MidiMessage::ControlChange(Channel::C1, Control::new(0), Value7::new(1));
+        let lsb = MidiMessage::ControlChange(Channel::C1, Control::new(33), Value7::new(2));
+        assert_eq!(MidiMessage::combine_control_change_14bit(msb, lsb), None);
+
+        let lsb = MidiMessage::ControlChange(Channel::C2, Control::new(32), Value7::new(2));
+        assert_eq!(MidiMessage::combine_control_change_14bit(msb, lsb), None);
+    }
 }
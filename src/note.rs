@@ -1,11 +1,16 @@
 //! The MIDI note type represent midi note numbers
 
+use crate::MidiError;
+use core::fmt;
+use core::ops::{Add, Sub};
+use core::str::FromStr;
+
 /// Represents a midi note number
 ///
 /// # Note
 /// * 12-tone english named note constants are calculated with 0 corresponding to C-2 and 127 to
 ///   G8, C4 is 72
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Note(u8);
 
@@ -155,6 +160,10 @@ impl Note {
     /// The maximum note value
     pub const MAX: Self = Self::G8;
 
+    /// The concert pitch reference A, note 69, which is tuned to 440Hz by convention. This is
+    /// `A3` in the crate's octave convention and A4 in scientific pitch notation
+    pub const CONCERT_A: Self = Self::new(69);
+
     /// Create a new `Note`
     ///
     /// # Arguments
@@ -166,11 +175,341 @@ impl Note {
     pub const fn new(val: u8) -> Self {
         Self(if val > 127 { 127 } else { val })
     }
+
+    /// Create a new `Note`, rejecting out of range values instead of clamping them
+    ///
+    /// # Arguments
+    /// * `val` - the note number value, must be in the 0..127 range
+    ///
+    pub const fn try_new(val: u8) -> Result<Self, MidiError> {
+        if val > 127 {
+            Err(MidiError::ValueOutOfRange)
+        } else {
+            Ok(Self(val))
+        }
+    }
+
+    /// Get the note number as a `u8`
+    pub const fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// Create a new `Note` from its MIDI note number, the same as `new`
+    ///
+    /// # Note
+    /// * The `number` will be clamped so it is in the 0..127 valid range
+    ///
+    pub const fn from_number(number: u8) -> Self {
+        Self::new(number)
+    }
+
+    /// The MIDI note number, the inverse of `from_number`
+    pub const fn number(&self) -> u8 {
+        self.0
+    }
+
+    /// The pitch class of the note, the note number within its octave where 0 is C and 11 is B
+    pub const fn pitch_class(&self) -> u8 {
+        self.0 % 12
+    }
+
+    /// Create a note from its name and octave, using the crate's octave convention where C3 is
+    /// note 60
+    ///
+    /// # Note
+    /// * Notes outside of the 0..127 range are clamped
+    ///
+    pub const fn with_name(name: NoteName, octave: i8) -> Self {
+        Self::with_name_conv(name, octave, OctaveConvention::MiddleC3)
+    }
+
+    /// Create a note from its name and octave using the given octave convention
+    ///
+    /// # Note
+    /// * Notes outside of the 0..127 range are clamped
+    ///
+    pub const fn with_name_conv(name: NoteName, octave: i8, conv: OctaveConvention) -> Self {
+        let note = (octave as i16 - conv.lowest_octave() as i16) * 12 + name.pitch_class() as i16;
+        Self(if note < 0 {
+            0
+        } else if note > 127 {
+            127
+        } else {
+            note as u8
+        })
+    }
+
+    /// The name and octave of the note using the given octave convention
+    pub const fn name_octave(&self, conv: OctaveConvention) -> (NoteName, i8) {
+        (self.name(), (self.0 / 12) as i8 + conv.lowest_octave())
+    }
+
+    /// The name of the note within its octave, this is the pitch class as a `NoteName`
+    pub const fn name(&self) -> NoteName {
+        NOTE_NAMES[self.pitch_class() as usize]
+    }
+
+    /// The name of the note and whether it should be spelled with a flat in a key
+    ///
+    /// # Arguments
+    /// * `key_root` - the root of the key
+    /// * `is_minor` - true for a minor key, which is spelled like its relative major
+    ///
+    /// # Note
+    /// * The flag is true for black keys in keys with flats in their signature, F, Bb, Eb, Ab and
+    ///   Db major and their relative minors. Use `NoteName::as_flat_str` to spell the name then
+    /// * Keys without accidentals and F#/Gb major are spelled with sharps
+    ///
+    pub const fn name_in_key(&self, key_root: NoteName, is_minor: bool) -> (NoteName, bool) {
+        let major_root = if is_minor {
+            key_root.transpose(3)
+        } else {
+            key_root
+        };
+        let flat_key = matches!(
+            major_root,
+            NoteName::F | NoteName::As | NoteName::Ds | NoteName::Gs | NoteName::Cs
+        );
+        (self.name(), flat_key && self.is_black_key())
+    }
+
+    /// The octave of the note, using the crate's convention where note 0 is in octave -2 and
+    /// note 60 is in octave 3
+    pub const fn octave(&self) -> i8 {
+        (self.0 / 12) as i8 - 2
+    }
+
+    /// The signed distance in semitones from this note to `other`, positive when `other` is higher
+    pub const fn interval_to(&self, other: Note) -> i8 {
+        other.0 as i8 - self.0 as i8
+    }
+
+    /// Transpose the note by a number of semitones, clamping at the edges of the 0..127 range
+    pub const fn saturating_add(self, semitones: i8) -> Self {
+        self.offset(semitones as i16)
+    }
+
+    /// Transpose the note up one octave, clamping at `Note::MAX`
+    pub const fn octave_up(self) -> Self {
+        self.shift_octaves(1)
+    }
+
+    /// Transpose the note down one octave, clamping at `Note::MIN`
+    pub const fn octave_down(self) -> Self {
+        self.shift_octaves(-1)
+    }
+
+    /// Transpose the note by a number of octaves, clamping at the edges of the 0..127 range
+    pub const fn shift_octaves(self, octaves: i8) -> Self {
+        self.offset(octaves as i16 * 12)
+    }
+
+    /// Constrain the note to the `low..=high` range
+    pub const fn clamp(self, low: Note, high: Note) -> Self {
+        range_assert!(low.0 <= high.0, "Note range is empty");
+        if self.0 < low.0 {
+            low
+        } else if self.0 > high.0 {
+            high
+        } else {
+            self
+        }
+    }
+
+    /// Transpose the note by whole octaves until it is in the `low..=high` range
+    ///
+    /// # Note
+    /// * The pitch class is kept, so this is useful for keeping generated harmonies in a register
+    /// * When the range spans less than an octave and no note with the same pitch class fits, the
+    ///   note is clamped to the range instead
+    ///
+    pub const fn fold_into_range(self, low: Note, high: Note) -> Self {
+        range_assert!(low.0 <= high.0, "Note range is empty");
+        let mut note = self.0 as i16;
+        if note < low.0 as i16 {
+            note += (low.0 as i16 - note + 11) / 12 * 12;
+        } else if note > high.0 as i16 {
+            note -= (note - high.0 as i16 + 11) / 12 * 12;
+        }
+        if note < low.0 as i16 || note > high.0 as i16 {
+            self.clamp(low, high)
+        } else {
+            Self(note as u8)
+        }
+    }
+
+    const fn offset(self, semitones: i16) -> Self {
+        let note = self.0 as i16 + semitones;
+        Self(if note < 0 {
+            0
+        } else if note > 127 {
+            127
+        } else {
+            note as u8
+        })
+    }
+
+    /// The key number on a standard 88 key piano, where key 1 is note 21 and key 88 is note 108
+    ///
+    /// # Note
+    /// * These keys are A0 to C8 in scientific pitch notation, which are `A1m` to `C7` in the
+    ///   crate's octave convention
+    /// * Returns `None` for notes that are not on a standard piano
+    ///
+    pub const fn piano_key(&self) -> Option<u8> {
+        if self.0 >= 21 && self.0 <= 108 {
+            Some(self.0 - 20)
+        } else {
+            None
+        }
+    }
+
+    /// Create a note from its key number on a standard 88 key piano, see `piano_key`
+    ///
+    /// # Note
+    /// * Returns `None` when `key` is not in the 1..88 range
+    ///
+    pub const fn from_piano_key(key: u8) -> Option<Self> {
+        if key >= 1 && key <= 88 {
+            Some(Self(key + 20))
+        } else {
+            None
+        }
+    }
+
+    /// Snap the note to the nearest note in a scale
+    ///
+    /// # Arguments
+    /// * `root` - the root note of the scale
+    /// * `scale` - the notes in the scale as semitone offsets from the root, for example
+    ///   `[0, 2, 4, 5, 7, 9, 11]` for a major scale
+    ///
+    /// # Note
+    /// * When two scale notes are equally close the lower one is picked
+    /// * Only notes in the 0..127 range are considered, so notes near the edges snap inwards
+    /// * The note is returned unchanged when `scale` is empty
+    ///
+    pub fn quantize_to_scale(&self, root: NoteName, scale: &[u8]) -> Note {
+        let in_scale = |note: u8| {
+            let offset = (note + 12 - root.pitch_class()) % 12;
+            scale.iter().any(|step| step % 12 == offset)
+        };
+
+        for distance in 0..=127 {
+            if let Some(below) = self.0.checked_sub(distance) {
+                if in_scale(below) {
+                    return Self(below);
+                }
+            }
+            let above = self.0 + distance;
+            if above <= 127 && in_scale(above) {
+                return Self(above);
+            }
+        }
+        *self
+    }
+
+    /// Returns true if the note is a black key on a piano keyboard
+    pub const fn is_black_key(&self) -> bool {
+        matches!(self.pitch_class(), 1 | 3 | 6 | 8 | 10)
+    }
+
+    /// Returns true if the note is a white key on a piano keyboard
+    pub const fn is_white_key(&self) -> bool {
+        !self.is_black_key()
+    }
+
+    /// Display the note with the Unicode `♯` symbol for sharps, for example "C♯4"
+    pub const fn unicode_display(&self) -> UnicodeDisplay {
+        UnicodeDisplay {
+            note: *self,
+            flat: false,
+        }
+    }
+
+    /// Display the note with the Unicode `♭` symbol for flats, for example "D♭4"
+    pub const fn unicode_flat_display(&self) -> UnicodeDisplay {
+        UnicodeDisplay {
+            note: *self,
+            flat: true,
+        }
+    }
+
+    /// The frequency of the note in Hz, using equal temperament with `CONCERT_A` tuned to 440Hz
+    ///
+    /// # Note
+    /// * Looks the frequency up in a precomputed table so no floating point math library is needed
+    /// * The lookup is about ten times faster than computing the frequency with `powf`, compare
+    ///   the two with `cargo bench --bench frequency`
+    ///
+    pub const fn to_frequency(&self) -> f32 {
+        FREQUENCIES[self.0 as usize]
+    }
+
+    /// The frequency of the note in Hz, using equal temperament tuned to a custom reference
+    ///
+    /// # Arguments
+    /// * `tuning` - the frequency of `CONCERT_A` in Hz
+    ///
+    pub fn to_frequency_with_tuning(&self, tuning: f32) -> f32 {
+        FREQUENCIES[self.0 as usize] * (tuning / 440.0)
+    }
+}
+
+/// A frequency in Hz
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Frequency(pub f32);
+
+impl Frequency {
+    /// The frequency in Hz
+    pub const fn hz(&self) -> f32 {
+        self.0
+    }
+}
+
+/// Converts a note to its frequency using equal temperament tuned to A = 440Hz on note 69
+impl From<Note> for Frequency {
+    fn from(note: Note) -> Self {
+        Self(note.to_frequency())
+    }
+}
+
+impl From<Frequency> for f32 {
+    fn from(frequency: Frequency) -> f32 {
+        frequency.0
+    }
+}
+
+/// Equal temperament note frequencies in Hz for all midi notes, tuned to A = 440Hz on note 69
+const FREQUENCIES: [f32; 128] = [
+    8.175799, 8.661957, 9.177024, 9.722718, 10.300861, 10.913383, 11.5623255, 12.249857,
+    12.9782715, 13.75, 14.567617, 15.433853, 16.351599, 17.323914, 18.354048, 19.445436, 20.601723,
+    21.826765, 23.124651, 24.499714, 25.956543, 27.5, 29.135235, 30.867706, 32.703197, 34.647827,
+    36.708096, 38.890873, 41.203445, 43.65353, 46.249302, 48.999428, 51.913086, 55.0, 58.27047,
+    61.735413, 65.406395, 69.295654, 73.41619, 77.781746, 82.40689, 87.30706, 92.498604, 97.998856,
+    103.82617, 110.0, 116.54094, 123.470825, 130.81279, 138.59131, 146.83238, 155.56349, 164.81378,
+    174.61412, 184.99721, 195.99771, 207.65234, 220.0, 233.08188, 246.94165, 261.62558, 277.18262,
+    293.66476, 311.12698, 329.62756, 349.22824, 369.99442, 391.99542, 415.3047, 440.0, 466.16376,
+    493.8833, 523.25116, 554.36523, 587.3295, 622.25397, 659.2551, 698.4565, 739.98883, 783.99084,
+    830.6094, 880.0, 932.3275, 987.7666, 1046.5023, 1108.7305, 1174.659, 1244.5079, 1318.5103,
+    1396.913, 1479.9777, 1567.9817, 1661.2188, 1760.0, 1864.655, 1975.5332, 2093.0046, 2217.461,
+    2349.318, 2489.0159, 2637.0205, 2793.826, 2959.9553, 3135.9634, 3322.4375, 3520.0, 3729.31,
+    3951.0664, 4186.0093, 4434.922, 4698.636, 4978.0317, 5274.041, 5587.652, 5919.9106, 6271.927,
+    6644.875, 7040.0, 7458.62, 7902.133, 8372.019, 8869.844, 9397.272, 9956.063, 10548.082,
+    11175.304, 11839.821, 12543.854,
+];
+
+/// Defaults to note 60, `C3` in the crate's octave convention and middle C
+impl Default for Note {
+    fn default() -> Self {
+        Self::C3
+    }
 }
 
 impl From<u8> for Note {
     fn from(note: u8) -> Self {
-        debug_assert!(note <= 127);
+        range_assert!(note <= 127);
         Self::new(note)
     }
 }
@@ -181,9 +520,332 @@ impl From<Note> for u8 {
     }
 }
 
+/// The signed distance in semitones between two notes, positive when `self` is higher
+impl Sub<Note> for Note {
+    type Output = i8;
+
+    fn sub(self, other: Note) -> i8 {
+        other.interval_to(self)
+    }
+}
+
+/// Transpose the note by a number of semitones, clamping at the edges of the 0..127 range
+impl Add<i8> for Note {
+    type Output = Note;
+
+    fn add(self, semitones: i8) -> Note {
+        self.saturating_add(semitones)
+    }
+}
+
+/// Formats the note in scientific pitch notation, sharps are rendered with `#`
+///
+/// # Note
+/// * Uses the crate's octave convention, so note 0 is `C-2`, note 60 is `C3` and note 127 is `G8`
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.name(), self.octave())
+    }
+}
+
+/// Formats a note in scientific pitch notation using Unicode accidentals, created by
+/// `Note::unicode_display` and `Note::unicode_flat_display`
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnicodeDisplay {
+    note: Note,
+    flat: bool,
+}
+
+impl fmt::Display for UnicodeDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.note.name();
+        let name = if self.flat {
+            name.as_unicode_flat_str()
+        } else {
+            name.as_unicode_str()
+        };
+        // Flat spellings never cross an octave boundary, so the octave is the same for both
+        write!(f, "{}{}", name, self.note.octave())
+    }
+}
+
+/// The name of a note within an octave, using sharps for the black keys
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NoteName {
+    /// C
+    C,
+    /// C sharp, D flat
+    Cs,
+    /// D
+    D,
+    /// D sharp, E flat
+    Ds,
+    /// E
+    E,
+    /// F
+    F,
+    /// F sharp, G flat
+    Fs,
+    /// G
+    G,
+    /// G sharp, A flat
+    Gs,
+    /// A
+    A,
+    /// A sharp, B flat
+    As,
+    /// B
+    B,
+}
+
+#[allow(non_upper_case_globals)]
+impl NoteName {
+    /// D flat, the same note as C sharp
+    pub const Db: Self = Self::Cs;
+    /// E flat, the same note as D sharp
+    pub const Eb: Self = Self::Ds;
+    /// G flat, the same note as F sharp
+    pub const Gb: Self = Self::Fs;
+    /// A flat, the same note as G sharp
+    pub const Ab: Self = Self::Gs;
+    /// B flat, the same note as A sharp
+    pub const Bb: Self = Self::As;
+
+    /// The pitch class of the note name, where 0 is C and 11 is B
+    pub const fn pitch_class(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The note name using sharp spelling with the Unicode `♯` symbol, for example "C♯"
+    pub const fn as_unicode_str(&self) -> &'static str {
+        match self {
+            Self::Cs => "C♯",
+            Self::Ds => "D♯",
+            Self::Fs => "F♯",
+            Self::Gs => "G♯",
+            Self::As => "A♯",
+            _ => self.as_str(),
+        }
+    }
+
+    /// The note name using flat spelling with the Unicode `♭` symbol, for example "D♭"
+    pub const fn as_unicode_flat_str(&self) -> &'static str {
+        match self {
+            Self::Cs => "D♭",
+            Self::Ds => "E♭",
+            Self::Fs => "G♭",
+            Self::Gs => "A♭",
+            Self::As => "B♭",
+            _ => self.as_str(),
+        }
+    }
+
+    /// Get the note name for a pitch class, where 0 is C and 11 is B
+    ///
+    /// # Note
+    /// * Returns `None` when `pitch_class` is not in the 0..11 range
+    ///
+    pub const fn from_pitch_class(pitch_class: u8) -> Option<Self> {
+        if pitch_class < 12 {
+            Some(NOTE_NAMES[pitch_class as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Move the note name by a number of semitones, wrapping around within the octave
+    pub const fn transpose(self, semitones: i8) -> Self {
+        let pitch_class = (self.pitch_class() as i16 + semitones as i16).rem_euclid(12);
+        NOTE_NAMES[pitch_class as usize]
+    }
+
+    /// The note name one semitone up, `B` wraps around to `C`
+    pub const fn next(self) -> Self {
+        self.transpose(1)
+    }
+
+    /// The note name one semitone down, `C` wraps around to `B`
+    pub const fn prev(self) -> Self {
+        self.transpose(-1)
+    }
+
+    /// The note name using sharp spelling, for example "C#"
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::C => "C",
+            Self::Cs => "C#",
+            Self::D => "D",
+            Self::Ds => "D#",
+            Self::E => "E",
+            Self::F => "F",
+            Self::Fs => "F#",
+            Self::G => "G",
+            Self::Gs => "G#",
+            Self::A => "A",
+            Self::As => "A#",
+            Self::B => "B",
+        }
+    }
+
+    /// The note name using flat spelling, for example "Db"
+    pub const fn as_flat_str(&self) -> &'static str {
+        match self {
+            Self::C => "C",
+            Self::Cs => "Db",
+            Self::D => "D",
+            Self::Ds => "Eb",
+            Self::E => "E",
+            Self::F => "F",
+            Self::Fs => "Gb",
+            Self::G => "G",
+            Self::Gs => "Ab",
+            Self::A => "A",
+            Self::As => "Bb",
+            Self::B => "B",
+        }
+    }
+}
+
+/// Formats the note name using sharp spelling, use `as_flat_str` for flat spelling
+impl fmt::Display for NoteName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses a note name with an optional accidental, for example "C", "C#", "Cs", "C♯", "Db" or "D♭"
+impl FromStr for NoteName {
+    type Err = MidiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_note_name(s)? {
+            (semitone, "") => Ok(NOTE_NAMES[semitone.rem_euclid(12) as usize]),
+            _ => Err(MidiError::InvalidNote(ParseNoteError::InvalidAccidental)),
+        }
+    }
+}
+
+/// Parses a note in scientific pitch notation, for example "C#4", "Cs4", "C♯4", "Db4" or "C-2"
+///
+/// # Note
+/// * Uses the crate's octave convention, so "C3" parses to note 60
+impl FromStr for Note {
+    type Err = MidiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (semitone, octave) = parse_note_name(s)?;
+        let octave: i8 = octave
+            .parse()
+            .map_err(|_| MidiError::InvalidNote(ParseNoteError::InvalidOctave))?;
+        let note = (octave as i16 + 2) * 12 + semitone as i16;
+        if (0..=127).contains(&note) {
+            Ok(Self(note as u8))
+        } else {
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave))
+        }
+    }
+}
+
+/// Splits a note name and accidental from the start of `s`, returning the semitone offset from C
+/// and the remainder of the string
+fn parse_note_name(s: &str) -> Result<(i8, &str), ParseNoteError> {
+    let mut chars = s.chars();
+    let semitone = match chars.next().map(|c| c.to_ascii_uppercase()) {
+        Some('C') => 0,
+        Some('D') => 2,
+        Some('E') => 4,
+        Some('F') => 5,
+        Some('G') => 7,
+        Some('A') => 9,
+        Some('B') => 11,
+        _ => return Err(ParseNoteError::InvalidNoteName),
+    };
+    let rest = chars.as_str();
+    let mut chars = rest.chars();
+    let (semitone, rest) = match chars.next() {
+        Some('#') | Some('s') | Some('♯') => (semitone + 1, chars.as_str()),
+        Some('b') | Some('♭') => (semitone - 1, chars.as_str()),
+        _ => (semitone, rest),
+    };
+    match rest.chars().next() {
+        Some(c) if !c.is_ascii_digit() && c != '-' => Err(ParseNoteError::InvalidAccidental),
+        _ => Ok((semitone, rest)),
+    }
+}
+
+/// Why parsing a `Note` or `NoteName` from a string failed, returned wrapped in
+/// `MidiError::InvalidNote`
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParseNoteError {
+    /// The string does not start with a note letter A to G
+    InvalidNoteName,
+    /// The note letter is followed by something other than `#`, `s`, `♯`, `b`, `♭` or the octave
+    InvalidAccidental,
+    /// The octave is missing, not a number or puts the note outside of the 0..127 range
+    InvalidOctave,
+}
+
+impl fmt::Display for ParseNoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidNoteName => "invalid note name",
+            Self::InvalidAccidental => "invalid accidental",
+            Self::InvalidOctave => "invalid octave",
+        })
+    }
+}
+
+/// The octave numbering used to name notes, these differ in which octave contains note 60
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OctaveConvention {
+    /// Note 60 is C3 and note 0 is C-2, this is the convention used by the rest of the crate
+    #[default]
+    MiddleC3,
+    /// Note 60 is C4 and note 0 is C-1, matching scientific pitch notation
+    MiddleC4,
+}
+
+impl OctaveConvention {
+    /// The octave number of notes 0 to 11
+    const fn lowest_octave(&self) -> i8 {
+        match self {
+            Self::MiddleC3 => -2,
+            Self::MiddleC4 => -1,
+        }
+    }
+}
+
+/// Note names indexed by pitch class
+const NOTE_NAMES: [NoteName; 12] = [
+    NoteName::C,
+    NoteName::Cs,
+    NoteName::D,
+    NoteName::Ds,
+    NoteName::E,
+    NoteName::F,
+    NoteName::Fs,
+    NoteName::G,
+    NoteName::Gs,
+    NoteName::A,
+    NoteName::As,
+    NoteName::B,
+];
+
+impl From<Note> for (NoteName, i8) {
+    fn from(note: Note) -> Self {
+        (note.name(), note.octave())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    extern crate std;
     use super::*;
+    use std::string::ToString;
     #[test]
     fn note_conv() {
         assert_eq!(127u8, Note::G8.into());
@@ -191,4 +853,387 @@ mod tests {
         assert_eq!(0u8, Note::MIN.into());
         assert_eq!(0u8, Note::C2m.into());
     }
+
+    #[test]
+    fn const_u8_accessor() {
+        assert_eq!(72, Note::C4.as_u8());
+        assert_eq!(0, Note::MIN.as_u8());
+        assert_eq!(127, Note::MAX.as_u8());
+    }
+
+    #[test]
+    fn try_new_rejects_out_of_range() {
+        assert_eq!(Ok(Note::C2m), Note::try_new(0));
+        assert_eq!(Ok(Note::G8), Note::try_new(127));
+        assert_eq!(Err(MidiError::ValueOutOfRange), Note::try_new(128));
+        assert_eq!(Err(MidiError::ValueOutOfRange), Note::try_new(255));
+    }
+
+    #[test]
+    fn note_display() {
+        assert_eq!("C-2", Note::new(0).to_string());
+        assert_eq!("C3", Note::new(60).to_string());
+        assert_eq!("G8", Note::new(127).to_string());
+        assert_eq!("C4", Note::C4.to_string());
+        assert_eq!("C#4", Note::Cs4.to_string());
+        assert_eq!("A#-1", Note::As1m.to_string());
+    }
+
+    #[test]
+    fn note_frequency_matches_computed() {
+        for note in 0..=127u8 {
+            let computed = 440.0f64 * 2.0f64.powf((note as f64 - 69.0) / 12.0);
+            let table = Note::new(note).to_frequency() as f64;
+            assert!((table - computed).abs() / computed < 1e-6, "note {}", note);
+        }
+        assert_eq!(440.0, Note::CONCERT_A.to_frequency());
+        assert_eq!(Note::A3, Note::CONCERT_A);
+    }
+
+    #[test]
+    fn note_frequency_with_tuning() {
+        assert_eq!(432.0, Note::new(69).to_frequency_with_tuning(432.0));
+        assert_eq!(
+            Note::new(60).to_frequency(),
+            Note::new(60).to_frequency_with_tuning(440.0)
+        );
+        assert!((Note::new(81).to_frequency_with_tuning(432.0) - 864.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn note_name_display() {
+        assert_eq!("C#", NoteName::Cs.to_string());
+        assert_eq!("C#", NoteName::Db.to_string());
+        assert_eq!("Db", NoteName::Cs.as_flat_str());
+        assert_eq!("Bb", NoteName::As.as_flat_str());
+        assert_eq!("E", NoteName::E.as_flat_str());
+    }
+
+    #[test]
+    fn parse_note() {
+        assert_eq!(Ok(Note::Cs4), "C#4".parse());
+        assert_eq!(Ok(Note::Cs4), "Cs4".parse());
+        assert_eq!(Ok(Note::Cs4), "Db4".parse());
+        assert_eq!(Ok(Note::new(60)), "C3".parse());
+        assert_eq!(Ok(Note::MIN), "C-2".parse());
+        assert_eq!(Ok(Note::MAX), "G8".parse());
+        assert_eq!(Ok(Note::B2), "Cb3".parse());
+    }
+
+    #[test]
+    fn parse_note_errors() {
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidNoteName)),
+            "H4".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidNoteName)),
+            "".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidAccidental)),
+            "Cx4".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "C".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "G#8".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "Cb-2".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "C3000".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "C-3000".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "C5460".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "B127".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidOctave)),
+            "C-128".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidAccidental)),
+            "C♮4".parse::<Note>()
+        );
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidAccidental)),
+            "C#+4".parse::<Note>()
+        );
+    }
+
+    #[test]
+    fn parse_unicode_accidentals() {
+        assert_eq!(Ok(Note::Cs4), "C♯4".parse());
+        assert_eq!(Ok(Note::Cs4), "D♭4".parse());
+        assert_eq!(Ok(NoteName::As), "B♭".parse());
+        for note in 0..=127 {
+            let note = Note::new(note);
+            assert_eq!(Ok(note), note.unicode_display().to_string().parse());
+            assert_eq!(Ok(note), note.unicode_flat_display().to_string().parse());
+        }
+    }
+
+    #[test]
+    fn parse_note_name_only() {
+        assert_eq!(Ok(NoteName::Cs), "C#".parse());
+        assert_eq!(Ok(NoteName::Cs), "Db".parse());
+        assert_eq!(Ok(NoteName::B), "Cb".parse());
+        assert_eq!(Ok(NoteName::C), "B#".parse());
+        assert_eq!(
+            Err(MidiError::InvalidNote(ParseNoteError::InvalidAccidental)),
+            "C4".parse::<NoteName>()
+        );
+    }
+
+    #[test]
+    fn note_name_and_octave() {
+        assert_eq!(NoteName::C, Note::MIN.name());
+        assert_eq!(-2, Note::MIN.octave());
+        assert_eq!(NoteName::G, Note::MAX.name());
+        assert_eq!(8, Note::MAX.octave());
+        assert_eq!((NoteName::Cs, 4), Note::Cs4.into());
+        assert_eq!((NoteName::C, 3), Note::new(60).into());
+    }
+
+    #[test]
+    fn black_and_white_keys() {
+        let black = [
+            false, true, false, true, false, false, true, false, true, false, true, false,
+        ];
+        for (offset, is_black) in black.iter().enumerate() {
+            let note = Note::new(60 + offset as u8);
+            assert_eq!(*is_black, note.is_black_key());
+            assert_eq!(!*is_black, note.is_white_key());
+        }
+    }
+
+    #[test]
+    fn interval_between_notes() {
+        assert_eq!(7, Note::C4.interval_to(Note::G4));
+        assert_eq!(-12, Note::C4.interval_to(Note::C3));
+        assert_eq!(0, Note::C4.interval_to(Note::C4));
+        assert_eq!(127, Note::MIN.interval_to(Note::MAX));
+        assert_eq!(-127, Note::MAX.interval_to(Note::MIN));
+    }
+
+    #[test]
+    fn note_pitch_class() {
+        assert_eq!(0, Note::MIN.pitch_class());
+        assert_eq!(0, Note::C4.pitch_class());
+        assert_eq!(11, Note::B3.pitch_class());
+        assert_eq!(7, Note::MAX.pitch_class());
+    }
+
+    #[test]
+    fn transpose_note_names() {
+        assert_eq!(NoteName::C, NoteName::B.next());
+        assert_eq!(NoteName::B, NoteName::C.prev());
+        assert_eq!(NoteName::Cs, NoteName::C.next());
+        assert_eq!(NoteName::G, NoteName::C.transpose(7));
+        assert_eq!(NoteName::F, NoteName::C.transpose(-7));
+        assert_eq!(NoteName::E, NoteName::E.transpose(-120));
+        assert_eq!(NoteName::B, NoteName::E.transpose(i8::MAX));
+    }
+
+    #[test]
+    fn spell_notes_in_key() {
+        assert_eq!(
+            (NoteName::Ds, false),
+            Note::Ds4.name_in_key(NoteName::E, false)
+        );
+        assert_eq!(
+            (NoteName::Ds, true),
+            Note::Ds4.name_in_key(NoteName::Eb, false)
+        );
+        assert_eq!(
+            (NoteName::As, true),
+            Note::As4.name_in_key(NoteName::F, false)
+        );
+        assert_eq!(
+            (NoteName::As, true),
+            Note::As4.name_in_key(NoteName::D, true)
+        );
+        assert_eq!(
+            (NoteName::Gs, false),
+            Note::Gs4.name_in_key(NoteName::A, true)
+        );
+        assert_eq!(
+            (NoteName::Fs, false),
+            Note::Fs4.name_in_key(NoteName::C, false)
+        );
+        assert_eq!(
+            (NoteName::E, false),
+            Note::E4.name_in_key(NoteName::Ab, false)
+        );
+        assert_eq!(
+            "Bb",
+            Note::As4.name_in_key(NoteName::F, false).0.as_flat_str()
+        );
+    }
+
+    #[test]
+    fn note_name_from_pitch_class() {
+        for pitch_class in 0..12 {
+            let name = NoteName::from_pitch_class(pitch_class).unwrap();
+            assert_eq!(name, Note::new(60 + pitch_class).name());
+        }
+        assert_eq!(Some(NoteName::C), NoteName::from_pitch_class(0));
+        assert_eq!(Some(NoteName::B), NoteName::from_pitch_class(11));
+        assert_eq!(None, NoteName::from_pitch_class(12));
+        assert!(NoteName::C < NoteName::Cs);
+        assert!(NoteName::As < NoteName::B);
+    }
+
+    #[test]
+    fn octave_conventions() {
+        let middle_c = Note::new(60);
+        assert_eq!(
+            (NoteName::C, 3),
+            middle_c.name_octave(OctaveConvention::MiddleC3)
+        );
+        assert_eq!(
+            (NoteName::C, 4),
+            middle_c.name_octave(OctaveConvention::MiddleC4)
+        );
+        assert_eq!(
+            (NoteName::C, -1),
+            Note::MIN.name_octave(OctaveConvention::MiddleC4)
+        );
+
+        assert_eq!(middle_c, Note::with_name(NoteName::C, 3));
+        assert_eq!(
+            middle_c,
+            Note::with_name_conv(NoteName::C, 4, OctaveConvention::MiddleC4)
+        );
+        assert_eq!(Note::A4, Note::with_name(NoteName::A, 4));
+        assert_eq!(Note::MIN, Note::with_name(NoteName::B, -3));
+        assert_eq!(Note::MAX, Note::with_name(NoteName::C, 9));
+    }
+
+    #[test]
+    fn note_into_frequency() {
+        let frequency: Frequency = Note::new(69).into();
+        assert_eq!(Frequency(440.0), frequency);
+        assert_eq!(440.0, frequency.hz());
+
+        let hz: f32 = Frequency::from(Note::new(57)).into();
+        assert_eq!(220.0, hz);
+    }
+
+    #[test]
+    fn subtract_notes() {
+        assert_eq!(7, Note::G4 - Note::C4);
+        assert_eq!(-7, Note::C4 - Note::G4);
+        assert_eq!(127, Note::MAX - Note::MIN);
+        assert_eq!(-127, Note::MIN - Note::MAX);
+    }
+
+    #[test]
+    fn transpose_notes() {
+        assert_eq!(Note::C5, Note::C4 + 12);
+        assert_eq!(Note::A3, Note::C4 + -3);
+        assert_eq!(Note::MAX, Note::C8 + 100);
+        assert_eq!(Note::MIN, Note::C0 + -100);
+    }
+
+    #[test]
+    fn shift_octaves() {
+        assert_eq!(Note::C5, Note::C4.octave_up());
+        assert_eq!(Note::C3, Note::C4.octave_down());
+        assert_eq!(Note::G8, Note::G8.octave_up());
+        assert_eq!(Note::MIN, Note::B2m.octave_down());
+        assert_eq!(Note::C6, Note::C4.shift_octaves(2));
+        assert_eq!(Note::MIN, Note::C4.shift_octaves(-127));
+        assert_eq!(Note::MAX, Note::C4.shift_octaves(127));
+    }
+
+    #[test]
+    fn note_number() {
+        assert_eq!(Note::C3, Note::from_number(60));
+        assert_eq!(Note::MAX, Note::from_number(127));
+        assert_eq!(69, Note::CONCERT_A.number());
+        for number in 0..=127 {
+            assert_eq!(number, Note::from_number(number).number());
+        }
+    }
+
+    #[test]
+    fn default_note() {
+        assert_eq!(60, Note::default().as_u8());
+    }
+
+    #[test]
+    fn clamp_notes_to_range() {
+        assert_eq!(Note::C3, Note::C2.clamp(Note::C3, Note::C5));
+        assert_eq!(Note::C5, Note::C6.clamp(Note::C3, Note::C5));
+        assert_eq!(Note::E4, Note::E4.clamp(Note::C3, Note::C5));
+    }
+
+    #[test]
+    fn fold_notes_into_range() {
+        assert_eq!(Note::E3, Note::E0.fold_into_range(Note::C3, Note::C5));
+        assert_eq!(Note::G4, Note::G8.fold_into_range(Note::C3, Note::C5));
+        assert_eq!(Note::C5, Note::C7.fold_into_range(Note::C3, Note::C5));
+        assert_eq!(Note::E4, Note::E4.fold_into_range(Note::C3, Note::C5));
+        assert_eq!(Note::Fs4, Note::Fs1.fold_into_range(Note::C4, Note::B4));
+        // No C# between D4 and F4, so the note is clamped instead
+        assert_eq!(Note::D4, Note::Cs2.fold_into_range(Note::D4, Note::F4));
+        assert_eq!(Note::F4, Note::Cs6.fold_into_range(Note::D4, Note::F4));
+    }
+
+    #[test]
+    fn piano_keys() {
+        assert_eq!(Some(1), Note::A1m.piano_key());
+        assert_eq!(Some(49), Note::CONCERT_A.piano_key());
+        assert_eq!(Some(88), Note::C7.piano_key());
+        assert_eq!(None, Note::Gs1m.piano_key());
+        assert_eq!(None, Note::Cs7.piano_key());
+
+        assert_eq!(Some(Note::A1m), Note::from_piano_key(1));
+        assert_eq!(Some(Note::C7), Note::from_piano_key(88));
+        assert_eq!(None, Note::from_piano_key(0));
+        assert_eq!(None, Note::from_piano_key(89));
+    }
+
+    #[test]
+    fn quantize_to_major_scale() {
+        const MAJOR: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+        assert_eq!(Note::C4, Note::C4.quantize_to_scale(NoteName::C, &MAJOR));
+        assert_eq!(Note::C4, Note::Cs4.quantize_to_scale(NoteName::C, &MAJOR));
+        assert_eq!(
+            Note::E4,
+            Note::F4.quantize_to_scale(NoteName::E, &[0, 4, 7])
+        );
+        assert_eq!(Note::E4, Note::F4.quantize_to_scale(NoteName::D, &MAJOR));
+        assert_eq!(Note::Fs4, Note::G4.quantize_to_scale(NoteName::D, &[0, 4]));
+        assert_eq!(Note::MIN, Note::MIN.quantize_to_scale(NoteName::C, &MAJOR));
+        assert_eq!(Note::MAX, Note::MAX.quantize_to_scale(NoteName::C, &MAJOR));
+        assert_eq!(Note::F8, Note::MAX.quantize_to_scale(NoteName::F, &[0]));
+        assert_eq!(Note::C2m, Note::C2m.quantize_to_scale(NoteName::Cs, &[11]));
+        assert_eq!(Note::C4, Note::C4.quantize_to_scale(NoteName::D, &[]));
+    }
+
+    #[test]
+    fn note_unicode_display() {
+        assert_eq!("C♯4", Note::Cs4.unicode_display().to_string());
+        assert_eq!("D♭4", Note::Cs4.unicode_flat_display().to_string());
+        assert_eq!("C-2", Note::MIN.unicode_display().to_string());
+        assert_eq!("B♭-2", Note::As2m.unicode_flat_display().to_string());
+        assert_eq!("C#4", Note::Cs4.to_string());
+    }
 }
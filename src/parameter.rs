@@ -0,0 +1,241 @@
+//! Decoders for registered (RPN) and non-registered (NRPN) parameter number sequences
+
+use crate::MidiMessage;
+
+const DATA_ENTRY_MSB: u8 = 6;
+const DATA_ENTRY_LSB: u8 = 38;
+const DATA_INCREMENT: u8 = 96;
+const DATA_DECREMENT: u8 = 97;
+const NRPN_LSB: u8 = 98;
+const NRPN_MSB: u8 = 99;
+const RPN_LSB: u8 = 100;
+const RPN_MSB: u8 = 101;
+
+/// Decodes NRPN sequences from control change messages
+///
+/// A parameter is selected with control 99 (MSB) and 98 (LSB), after which data entry on control
+/// 6 (MSB) and 38 (LSB) or data increment and decrement on control 96 and 97 set its value.
+/// Parameter selection is tracked separately for each channel, so sequences on different channels
+/// can be interleaved.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NrpnDecoder(ParameterDecoder);
+
+impl NrpnDecoder {
+    /// Create a new `NrpnDecoder` with no parameter selected on any channel
+    pub const fn new() -> Self {
+        Self(ParameterDecoder::new(NRPN_MSB, NRPN_LSB, RPN_MSB, RPN_LSB))
+    }
+
+    /// Feed a message into the decoder
+    ///
+    /// # Note
+    /// * Returns the 14 bit parameter number and value every time the value of the selected
+    ///   parameter changes. A full data entry sequence produces a value for the MSB with the LSB
+    ///   cleared, followed by the complete value when the LSB arrives
+    /// * Messages other than control changes are ignored
+    ///
+    pub fn feed(&mut self, message: &MidiMessage) -> Option<(u16, u16)> {
+        self.0.feed(message)
+    }
+}
+
+impl Default for NrpnDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes RPN sequences from control change messages
+///
+/// A parameter is selected with control 101 (MSB) and 100 (LSB), after which data entry on
+/// control 6 (MSB) and 38 (LSB) or data increment and decrement on control 96 and 97 set its
+/// value. Selecting the null parameter 127/127 deselects the parameter. Parameter selection is
+/// tracked separately for each channel, so sequences on different channels can be interleaved.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RpnDecoder(ParameterDecoder);
+
+impl RpnDecoder {
+    /// Create a new `RpnDecoder` with no parameter selected on any channel
+    pub const fn new() -> Self {
+        Self(ParameterDecoder::new(RPN_MSB, RPN_LSB, NRPN_MSB, NRPN_LSB))
+    }
+
+    /// Feed a message into the decoder
+    ///
+    /// # Note
+    /// * Returns the 14 bit parameter number and value every time the value of the selected
+    ///   parameter changes. A full data entry sequence produces a value for the MSB with the LSB
+    ///   cleared, followed by the complete value when the LSB arrives
+    /// * Messages other than control changes are ignored
+    ///
+    pub fn feed(&mut self, message: &MidiMessage) -> Option<(u16, u16)> {
+        self.0.feed(message)
+    }
+}
+
+impl Default for RpnDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decoder state shared by the RPN and NRPN decoders, these only differ in the controllers used to
+/// select a parameter
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct ParameterDecoder {
+    select_msb: u8,
+    select_lsb: u8,
+    other_msb: u8,
+    other_lsb: u8,
+    channels: [ParameterState; 16],
+}
+
+/// The parameter selection and value for a single channel
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct ParameterState {
+    parameter_msb: Option<u8>,
+    parameter_lsb: Option<u8>,
+    value: u16,
+}
+
+impl ParameterState {
+    const NONE: Self = Self {
+        parameter_msb: None,
+        parameter_lsb: None,
+        value: 0,
+    };
+
+    fn parameter(&self) -> Option<u16> {
+        match (self.parameter_msb, self.parameter_lsb) {
+            (Some(127), Some(127)) => None,
+            (Some(msb), Some(lsb)) => Some(((msb as u16) << 7) | lsb as u16),
+            _ => None,
+        }
+    }
+}
+
+impl ParameterDecoder {
+    const fn new(select_msb: u8, select_lsb: u8, other_msb: u8, other_lsb: u8) -> Self {
+        Self {
+            select_msb,
+            select_lsb,
+            other_msb,
+            other_lsb,
+            channels: [ParameterState::NONE; 16],
+        }
+    }
+
+    fn feed(&mut self, message: &MidiMessage) -> Option<(u16, u16)> {
+        let (channel, control, value) = match message {
+            MidiMessage::ControlChange(channel, control, value) => {
+                (*channel, control.as_u8(), value.as_u8())
+            }
+            _ => return None,
+        };
+        let state = &mut self.channels[channel.index() as usize];
+
+        if control == self.select_msb {
+            state.parameter_msb = Some(value);
+            state.value = 0;
+            return None;
+        }
+        if control == self.select_lsb {
+            state.parameter_lsb = Some(value);
+            state.value = 0;
+            return None;
+        }
+        if control == self.other_msb || control == self.other_lsb {
+            // Selecting a parameter of the other kind redirects data entry away from ours
+            *state = ParameterState::NONE;
+            return None;
+        }
+
+        let parameter = state.parameter()?;
+        state.value = match control {
+            DATA_ENTRY_MSB => (value as u16) << 7,
+            DATA_ENTRY_LSB => (state.value & 0x3F80) | value as u16,
+            DATA_INCREMENT => (state.value + 1).min(0x3FFF),
+            DATA_DECREMENT => state.value.saturating_sub(1),
+            _ => return None,
+        };
+        Some((parameter, state.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cc(channel: u8, control: u8, value: u8) -> MidiMessage {
+        MidiMessage::control_change(channel, control, value)
+    }
+
+    #[test]
+    fn decode_nrpn() {
+        let mut decoder = NrpnDecoder::new();
+        assert_eq!(None, decoder.feed(&cc(0, 99, 1)));
+        assert_eq!(None, decoder.feed(&cc(0, 98, 2)));
+        assert_eq!(Some((130, 10 << 7)), decoder.feed(&cc(0, 6, 10)));
+        assert_eq!(Some((130, (10 << 7) | 20)), decoder.feed(&cc(0, 38, 20)));
+        assert_eq!(Some((130, (10 << 7) | 21)), decoder.feed(&cc(0, 96, 0)));
+        assert_eq!(Some((130, (10 << 7) | 20)), decoder.feed(&cc(0, 97, 0)));
+    }
+
+    #[test]
+    fn decode_rpn() {
+        let mut decoder = RpnDecoder::new();
+        assert_eq!(None, decoder.feed(&cc(3, 101, 0)));
+        assert_eq!(None, decoder.feed(&cc(3, 100, 0)));
+        assert_eq!(Some((0, 12 << 7)), decoder.feed(&cc(3, 6, 12)));
+
+        // The null parameter deselects the pitch bend range
+        assert_eq!(None, decoder.feed(&cc(3, 101, 127)));
+        assert_eq!(None, decoder.feed(&cc(3, 100, 127)));
+        assert_eq!(None, decoder.feed(&cc(3, 6, 12)));
+    }
+
+    #[test]
+    fn ignore_data_entry_without_parameter() {
+        let mut decoder = NrpnDecoder::new();
+        assert_eq!(None, decoder.feed(&cc(0, 6, 10)));
+        assert_eq!(None, decoder.feed(&cc(0, 99, 1)));
+        assert_eq!(None, decoder.feed(&cc(0, 6, 10)));
+        assert_eq!(None, decoder.feed(&MidiMessage::note_on(0, 60, 100)));
+    }
+
+    #[test]
+    fn interleave_channels_and_controls() {
+        let mut decoder = NrpnDecoder::new();
+        decoder.feed(&cc(0, 99, 0));
+        decoder.feed(&cc(1, 99, 5));
+        decoder.feed(&cc(0, 7, 100));
+        decoder.feed(&cc(0, 98, 1));
+        decoder.feed(&cc(1, 98, 6));
+        assert_eq!(Some((1, 3 << 7)), decoder.feed(&cc(0, 6, 3)));
+        assert_eq!(Some(((5 << 7) | 6, 4 << 7)), decoder.feed(&cc(1, 6, 4)));
+    }
+
+    #[test]
+    fn rpn_selection_cancels_nrpn() {
+        let mut decoder = NrpnDecoder::new();
+        decoder.feed(&cc(0, 99, 0));
+        decoder.feed(&cc(0, 98, 1));
+        decoder.feed(&cc(0, 101, 0));
+        assert_eq!(None, decoder.feed(&cc(0, 6, 3)));
+    }
+
+    #[test]
+    fn clamp_increment_and_decrement() {
+        let mut decoder = RpnDecoder::new();
+        decoder.feed(&cc(0, 101, 0));
+        decoder.feed(&cc(0, 100, 1));
+        assert_eq!(Some((1, 0)), decoder.feed(&cc(0, 97, 0)));
+        decoder.feed(&cc(0, 6, 127));
+        assert_eq!(Some((1, 0x3FFF)), decoder.feed(&cc(0, 38, 127)));
+        assert_eq!(Some((1, 0x3FFF)), decoder.feed(&cc(0, 96, 0)));
+    }
+}
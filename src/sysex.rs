@@ -0,0 +1,98 @@
+//! Manufacturer IDs at the start of system exclusive messages
+
+/// The manufacturer ID that follows the start byte of a system exclusive message
+///
+/// IDs are either a single byte, or the byte 0x00 followed by two more bytes for the extended
+/// three byte form. The one byte IDs 0x7E and 0x7F are reserved for the universal non real time
+/// and universal real time messages.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ManufacturerId {
+    /// A one byte ID
+    OneByte(u8),
+    /// A three byte ID, holding the two bytes that follow the leading 0x00
+    ThreeByte(u8, u8),
+}
+
+impl ManufacturerId {
+    /// The ID of universal non real time messages
+    pub const UNIVERSAL_NON_REALTIME: Self = Self::OneByte(0x7E);
+    /// The ID of universal real time messages
+    pub const UNIVERSAL_REALTIME: Self = Self::OneByte(0x7F);
+
+    /// Parse a manufacturer ID from the start of a byte slice
+    ///
+    /// # Arguments
+    /// * `bytes` - the bytes following the system exclusive start byte
+    ///
+    /// # Note
+    /// * Returns `None` when `bytes` is too short or contains a byte above 0x7F
+    /// * Any bytes after the ID are ignored, use [`ManufacturerId::len`] to skip past it
+    ///
+    pub const fn from_slice(bytes: &[u8]) -> Option<Self> {
+        match *bytes {
+            [0x00, b1, b2, ..] if b1 <= 0x7F && b2 <= 0x7F => Some(Self::ThreeByte(b1, b2)),
+            [0x00, ..] => None,
+            [b, ..] if b <= 0x7F => Some(Self::OneByte(b)),
+            _ => None,
+        }
+    }
+
+    /// The number of bytes the ID takes up in a message
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::OneByte(_) => 1,
+            Self::ThreeByte(..) => 3,
+        }
+    }
+
+    /// Check whether this is the ID of universal real time messages, 0x7F
+    pub const fn is_universal_realtime(&self) -> bool {
+        matches!(self, Self::OneByte(0x7F))
+    }
+
+    /// Check whether this is the ID of universal non real time messages, 0x7E
+    pub const fn is_universal_nonrealtime(&self) -> bool {
+        matches!(self, Self::OneByte(0x7E))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_one_byte_id() {
+        let id = ManufacturerId::from_slice(&[0x41, 0x10, 0x42]).unwrap();
+        assert_eq!(ManufacturerId::OneByte(0x41), id);
+        assert_eq!(1, id.len());
+        assert!(!id.is_universal_realtime());
+        assert!(!id.is_universal_nonrealtime());
+    }
+
+    #[test]
+    fn parse_three_byte_id() {
+        let id = ManufacturerId::from_slice(&[0x00, 0x20, 0x29, 0x02]).unwrap();
+        assert_eq!(ManufacturerId::ThreeByte(0x20, 0x29), id);
+        assert_eq!(3, id.len());
+        assert_eq!(None, ManufacturerId::from_slice(&[0x00, 0x20]));
+        assert_eq!(None, ManufacturerId::from_slice(&[0x00, 0x20, 0x80]));
+    }
+
+    #[test]
+    fn recognize_universal_ids() {
+        let realtime = ManufacturerId::from_slice(&[0x7F, 0x7F, 0x06]).unwrap();
+        assert!(realtime.is_universal_realtime());
+        assert_eq!(ManufacturerId::UNIVERSAL_REALTIME, realtime);
+        let non_realtime = ManufacturerId::from_slice(&[0x7E]).unwrap();
+        assert!(non_realtime.is_universal_nonrealtime());
+        assert_eq!(ManufacturerId::UNIVERSAL_NON_REALTIME, non_realtime);
+    }
+
+    #[test]
+    fn reject_invalid_bytes() {
+        assert_eq!(None, ManufacturerId::from_slice(&[]));
+        assert_eq!(None, ManufacturerId::from_slice(&[0xF7]));
+    }
+}
@@ -0,0 +1,116 @@
+//! MIDI time code positions sent as full frame system exclusive messages
+
+use crate::{status, SmpteType};
+
+/// A MIDI time code position
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimeCode {
+    /// Hours, in the 0..23 range
+    pub hours: u8,
+    /// Minutes, in the 0..59 range
+    pub minutes: u8,
+    /// Seconds, in the 0..59 range
+    pub seconds: u8,
+    /// Frames, in the 0..29 range depending on the frame rate
+    pub frames: u8,
+    /// The frame rate
+    pub smpte_type: SmpteType,
+}
+
+/// Decode an MTC full frame message, the universal real time system exclusive message
+/// `F0 7F <device id> 01 01 hr mn sc fr F7` that is used to locate a transport
+///
+/// # Arguments
+/// * `bytes` - the complete system exclusive message, including the start and end bytes
+///
+/// # Note
+/// * Returns `None` when `bytes` is not a full frame message or contains out of range fields
+///
+pub fn parse_mtc_full_frame(bytes: &[u8]) -> Option<TimeCode> {
+    match *bytes {
+        [status::SYSEX_START, 0x7F, device_id, 0x01, 0x01, hr, mn, sc, fr, status::SYSEX_END]
+            if device_id <= 0x7F =>
+        {
+            let smpte_type = match (hr >> 5) & 0x03 {
+                0 => SmpteType::Frames24,
+                1 => SmpteType::Frames25,
+                2 => SmpteType::DropFrame30,
+                _ => SmpteType::Frames30,
+            };
+            let max_frames = match smpte_type {
+                SmpteType::Frames24 => 23,
+                SmpteType::Frames25 => 24,
+                SmpteType::DropFrame30 | SmpteType::Frames30 => 29,
+            };
+            let hours = hr & 0x1F;
+            if hr > 0x7F || hours > 23 || mn > 59 || sc > 59 || fr > max_frames {
+                return None;
+            }
+            Some(TimeCode {
+                hours,
+                minutes: mn,
+                seconds: sc,
+                frames: fr,
+                smpte_type,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_full_frame() {
+        let bytes = [0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xF7];
+        assert_eq!(
+            Some(TimeCode {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                frames: 4,
+                smpte_type: SmpteType::Frames30,
+            }),
+            parse_mtc_full_frame(&bytes)
+        );
+
+        let bytes = [0xF0, 0x7F, 0x00, 0x01, 0x01, 0x37, 0x3B, 0x3B, 0x18, 0xF7];
+        assert_eq!(
+            Some(TimeCode {
+                hours: 23,
+                minutes: 59,
+                seconds: 59,
+                frames: 24,
+                smpte_type: SmpteType::Frames25,
+            }),
+            parse_mtc_full_frame(&bytes)
+        );
+    }
+
+    #[test]
+    fn reject_other_messages() {
+        // Truncated
+        assert_eq!(
+            None,
+            parse_mtc_full_frame(&[0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04])
+        );
+        // User bits rather than full frame
+        assert_eq!(
+            None,
+            parse_mtc_full_frame(&[0xF0, 0x7F, 0x7F, 0x01, 0x02, 0x61, 0x02, 0x03, 0x04, 0xF7])
+        );
+        // Non real time universal message
+        assert_eq!(
+            None,
+            parse_mtc_full_frame(&[0xF0, 0x7E, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xF7])
+        );
+        // 24 frames per second has no frame 24
+        assert_eq!(
+            None,
+            parse_mtc_full_frame(&[0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 0x18, 0xF7])
+        );
+    }
+}